@@ -0,0 +1,9 @@
+//! Google Congestion Control (GCC): a delay-based bandwidth estimator plus the AIMD rate
+//! controller it drives, replacing the POC's synthetic `now % N` network measurements and fixed
+//! protocol-update timer with signals derived from real per-packet send/arrival timestamps.
+
+mod aimd;
+mod delay;
+
+pub use aimd::{AimdRateController, RateControlState};
+pub use delay::{DelayGradientTrendline, GccDelayEstimator, OveruseSignal, PacketTiming, TrendEstimatorFlavor};
@@ -0,0 +1,160 @@
+//! GCC-style AIMD rate controller: turns the delay estimator's overuse/underuse/normal signal
+//! into an Increase/Hold/Decrease rate-control state and adjusts a target bitrate accordingly,
+//! so protocol regeneration responds to actual congestion signals instead of a wall-clock tick.
+
+use std::time::Instant;
+
+use super::OveruseSignal;
+
+/// Minimum additive increase step, used when near the last decrease rate even if the expected
+/// packet size per frame is smaller
+const MIN_ADDITIVE_STEP_BPS: f64 = 1000.0;
+
+/// Per-second multiplicative increase factor used away from the last decrease rate
+const MULTIPLICATIVE_INCREASE_FACTOR: f64 = 1.08;
+
+/// Fraction applied to the measured incoming throughput on a decrease
+const DECREASE_FACTOR: f64 = 0.85;
+
+/// Fractional distance from the last decrease rate within which the controller treats itself as
+/// "near" it and switches from multiplicative to additive increase
+const NEAR_LAST_DECREASE_FRACTION: f64 = 0.05;
+
+/// Rate-control state, mirroring the GCC draft's Increase/Hold/Decrease states
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateControlState {
+    /// Grow the target rate, additively near the last decrease rate and multiplicatively
+    /// otherwise
+    Increase,
+    /// Leave the target rate unchanged
+    Hold,
+    /// Cut the target rate to a fraction of the measured incoming throughput
+    Decrease,
+}
+
+impl RateControlState {
+    fn from_signal(signal: OveruseSignal) -> Self {
+        match signal {
+            OveruseSignal::Normal => RateControlState::Increase,
+            OveruseSignal::Overuse => RateControlState::Decrease,
+            OveruseSignal::Underuse => RateControlState::Hold,
+        }
+    }
+}
+
+/// AIMD target-rate controller driven by a [`GccDelayEstimator`](super::GccDelayEstimator)'s
+/// overuse signal
+pub struct AimdRateController {
+    rate_bps: f64,
+    min_bitrate_bps: f64,
+    max_bitrate_bps: f64,
+    last_decrease_rate_bps: Option<f64>,
+    state: RateControlState,
+    last_update: Option<Instant>,
+}
+
+impl AimdRateController {
+    /// Create a new controller starting at `initial_bps`, clamped to `[min_bitrate_bps,
+    /// max_bitrate_bps]`
+    pub fn new(initial_bps: f64, min_bitrate_bps: f64, max_bitrate_bps: f64) -> Self {
+        Self {
+            rate_bps: initial_bps.clamp(min_bitrate_bps, max_bitrate_bps),
+            min_bitrate_bps,
+            max_bitrate_bps,
+            last_decrease_rate_bps: None,
+            state: RateControlState::Hold,
+            last_update: None,
+        }
+    }
+
+    /// Advance the controller with a fresh overuse `signal`, the measured incoming throughput
+    /// (bits/sec), and the expected packet size per frame (bits), returning the resulting state
+    pub fn update(&mut self, signal: OveruseSignal, measured_incoming_bps: f64, expected_packet_size_bits: f64, now: Instant) -> RateControlState {
+        let elapsed_secs = self.last_update.map(|t| now.duration_since(t).as_secs_f64()).unwrap_or(1.0);
+        self.last_update = Some(now);
+
+        let state = RateControlState::from_signal(signal);
+        match state {
+            RateControlState::Increase => {
+                let near_last_decrease = self
+                    .last_decrease_rate_bps
+                    .is_some_and(|last| (self.rate_bps - last).abs() <= last * NEAR_LAST_DECREASE_FRACTION);
+
+                if near_last_decrease {
+                    self.rate_bps += expected_packet_size_bits.max(MIN_ADDITIVE_STEP_BPS);
+                } else {
+                    self.rate_bps *= MULTIPLICATIVE_INCREASE_FACTOR.powf(elapsed_secs);
+                }
+            }
+            RateControlState::Decrease => {
+                self.rate_bps = measured_incoming_bps * DECREASE_FACTOR;
+                self.last_decrease_rate_bps = Some(self.rate_bps);
+            }
+            RateControlState::Hold => {}
+        }
+
+        self.rate_bps = self.rate_bps.clamp(self.min_bitrate_bps, self.max_bitrate_bps);
+        self.state = state;
+        state
+    }
+
+    /// Current target rate, in bits/sec
+    pub fn rate_bps(&self) -> f64 {
+        self.rate_bps
+    }
+
+    /// Most recent rate-control state
+    pub fn state(&self) -> RateControlState {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn overuse_cuts_rate_to_fraction_of_measured_throughput() {
+        let mut controller = AimdRateController::new(100_000.0, 10_000.0, 1_000_000.0);
+        let now = Instant::now();
+
+        let state = controller.update(OveruseSignal::Overuse, 200_000.0, 5000.0, now);
+
+        assert_eq!(state, RateControlState::Decrease);
+        assert_eq!(controller.rate_bps(), 200_000.0 * DECREASE_FACTOR);
+    }
+
+    #[test]
+    fn underuse_holds_the_rate() {
+        let mut controller = AimdRateController::new(100_000.0, 10_000.0, 1_000_000.0);
+        let now = Instant::now();
+
+        let state = controller.update(OveruseSignal::Underuse, 200_000.0, 5000.0, now);
+
+        assert_eq!(state, RateControlState::Hold);
+        assert_eq!(controller.rate_bps(), 100_000.0);
+    }
+
+    #[test]
+    fn normal_increases_multiplicatively_away_from_last_decrease() {
+        let mut controller = AimdRateController::new(100_000.0, 10_000.0, 1_000_000.0);
+        let now = Instant::now();
+
+        controller.update(OveruseSignal::Normal, 0.0, 5000.0, now);
+
+        assert!(controller.rate_bps() > 100_000.0);
+    }
+
+    #[test]
+    fn rate_is_clamped_to_configured_bounds() {
+        let mut controller = AimdRateController::new(100_000.0, 10_000.0, 150_000.0);
+        let now = Instant::now();
+
+        // the first update has no previous one to measure from, so prime it before the 10s gap
+        controller.update(OveruseSignal::Normal, 0.0, 5000.0, now);
+        controller.update(OveruseSignal::Normal, 0.0, 5000.0, now + Duration::from_secs(10));
+
+        assert_eq!(controller.rate_bps(), 150_000.0);
+    }
+}
@@ -6,7 +6,11 @@ use std::thread;
 
 // Import simulation module
 mod simulation;
-use simulation::{LargeScaleSimulator, SimulationConfig};
+use simulation::{DemoConfig, LargeScaleSimulator};
+
+// Import the GCC delay-based bandwidth estimator
+mod gcc;
+use gcc::{AimdRateController, GccDelayEstimator, OveruseSignal, PacketTiming, RateControlState, TrendEstimatorFlavor};
 
 // Import the public API from unrealnet-core
 use unrealnet_core::dynphys::{
@@ -33,6 +37,42 @@ struct PerformanceResult {
     transfer_time: f64,
     /// Active protocol ID if any
     protocol_id: Option<String>,
+    /// Transport-wide feedback captured for this cycle, if any packets were simulated, so the
+    /// congestion-control decisions taken from it can be reconstructed offline
+    transport_feedback: Option<TransportFeedback>,
+}
+
+/// One measurement cycle's transport-wide feedback: each simulated packet's transport sequence
+/// number and its send/arrival timestamps, mirroring the feedback a transport-cc-style receiver
+/// would report back to the sender
+#[derive(Debug, Clone)]
+struct TransportFeedback {
+    /// (transport sequence number, send timestamp, arrival timestamp) for each simulated packet,
+    /// in send order
+    packets: Vec<(u16, Instant, Instant)>,
+}
+
+impl TransportFeedback {
+    /// Spread (max − min) of inter-packet delay variation `d(i)` across this cycle's packets, in
+    /// milliseconds — used as the jitter measurement instead of a synthetic `now % 20`
+    fn jitter_spread_ms(&self) -> f64 {
+        let mut deltas_ms = Vec::with_capacity(self.packets.len().saturating_sub(1));
+        for window in self.packets.windows(2) {
+            let (_, sent_a, arrived_a) = window[0];
+            let (_, sent_b, arrived_b) = window[1];
+            let departure_delta_ms = sent_b.duration_since(sent_a).as_secs_f64() * 1000.0;
+            let arrival_delta_ms = arrived_b.duration_since(arrived_a).as_secs_f64() * 1000.0;
+            deltas_ms.push(arrival_delta_ms - departure_delta_ms);
+        }
+
+        if deltas_ms.len() < 2 {
+            return 0.0;
+        }
+
+        let max = deltas_ms.iter().cloned().fold(f64::MIN, f64::max);
+        let min = deltas_ms.iter().cloned().fold(f64::MAX, f64::min);
+        max - min
+    }
 }
 
 /// Real-world POC application for Dynamic Protocol generator
@@ -47,6 +87,29 @@ struct DynamicProtocolPoc {
     adaptation_enabled: bool,
     /// Network measurements
     network_conditions: Vec<NetworkCondition>,
+    /// GCC delay-based bandwidth estimator driving the `bandwidth` network condition from real
+    /// per-packet send/arrival timestamps
+    gcc_estimator: GccDelayEstimator,
+    /// Most recent overuse-detector classification from `gcc_estimator`
+    last_overuse_signal: OveruseSignal,
+    /// AIMD target-rate controller driven by `last_overuse_signal`, replacing the fixed
+    /// protocol-update timer with an event-driven trigger
+    rate_controller: AimdRateController,
+    /// Minimum clamp applied to `rate_controller`'s target rate
+    min_bitrate_bps: f64,
+    /// Maximum clamp applied to `rate_controller`'s target rate
+    max_bitrate_bps: f64,
+    /// Minimum change in `rate_controller`'s target rate since the last protocol regeneration
+    /// that alone is enough to trigger another one
+    rate_change_delta_bps: f64,
+    /// `rate_controller`'s target rate as of the last protocol regeneration
+    last_triggered_rate_bps: f64,
+    /// Transport-wide feedback captured during the most recently measured cycle, carried into
+    /// `measure_performance`'s `PerformanceResult` for offline per-packet analysis
+    last_transport_feedback: Option<TransportFeedback>,
+    /// Next transport sequence number to assign, wrapping across the whole run like a
+    /// transport-cc sequence number rather than resetting every cycle
+    next_transport_seq: u16,
 }
 
 impl DynamicProtocolPoc {
@@ -58,12 +121,25 @@ impl DynamicProtocolPoc {
         // Add physics models
         Self::configure_physics_models(&mut protocol_engine);
         
+        let min_bitrate_bps = 100_000.0; // 100 Kbps
+        let max_bitrate_bps = 100_000_000.0; // 100 Mbps
+        let initial_bitrate_bps = 5_000_000.0; // 5 Mbps
+
         Self {
             protocol_engine,
             performance_results: Arc::new(Mutex::new(Vec::new())),
             active_protocol: None,
             adaptation_enabled: true,
             network_conditions: Vec::new(),
+            gcc_estimator: GccDelayEstimator::new(),
+            last_overuse_signal: OveruseSignal::Normal,
+            rate_controller: AimdRateController::new(initial_bitrate_bps, min_bitrate_bps, max_bitrate_bps),
+            min_bitrate_bps,
+            max_bitrate_bps,
+            rate_change_delta_bps: 500_000.0, // 500 Kbps
+            last_triggered_rate_bps: initial_bitrate_bps,
+            last_transport_feedback: None,
+            next_transport_seq: 0,
         }
     }
     
@@ -131,7 +207,21 @@ impl DynamicProtocolPoc {
         self.adaptation_enabled = enabled;
         println!("Protocol adaptation {}", if enabled { "enabled" } else { "disabled" });
     }
+
+    /// Select the delay-trend estimator flavor driving `gcc_estimator`, so the baseline-vs-adapted
+    /// comparison in `save_results` can be A/B'd against Kalman or linear-regression trend tracking
+    pub fn set_estimator(&mut self, flavor: TrendEstimatorFlavor) {
+        self.gcc_estimator.set_flavor(flavor);
+        println!("Delay estimator flavor set to {:?}", flavor);
+    }
     
+    /// Number of packets simulated per measurement cycle to drive the GCC estimator
+    const PACKETS_PER_MEASUREMENT: u32 = 20;
+    /// Spacing between simulated packet departures
+    const PACKET_SEND_INTERVAL: Duration = Duration::from_millis(5);
+    /// Size assumed for each simulated packet
+    const PACKET_SIZE_BYTES: usize = 1200;
+
     /// Simulate real network measurements
     fn simulate_network_measurements(&mut self) -> Vec<NetworkCondition> {
         // Get current timestamp
@@ -139,9 +229,9 @@ impl DynamicProtocolPoc {
             .duration_since(UNIX_EPOCH)
             .unwrap_or_else(|_| Duration::from_secs(0))
             .as_secs();
-        
+
         let mut conditions = Vec::new();
-        
+
         // Latency measurement - simulate varying latency
         let latency = 50.0 + (now % 50) as f64;
         conditions.push(NetworkCondition {
@@ -149,14 +239,53 @@ impl DynamicProtocolPoc {
             value: self.normalize_latency(latency),
             timestamp: now,
         });
-        
-        // Bandwidth measurement - simulate varying bandwidth
-        let bandwidth = 5000.0 + (now % 5000) as f64;
+
+        // Bandwidth measurement - simulate a burst of packets with a wandering one-way delay
+        // and feed each one through the GCC delay-based estimator, so the condition reflects a
+        // genuine measurement rather than a synthetic `now % N`. Each packet's transport
+        // sequence number and send/arrival timestamps are kept as this cycle's transport-wide
+        // feedback, driving the jitter measurement below and surfacing in `save_results`.
+        let base_delay_ms = 10.0 + (now % 50) as f64;
+        let send_base = Instant::now();
+        let mut feedback_packets = Vec::with_capacity(Self::PACKETS_PER_MEASUREMENT as usize);
+        for i in 0..Self::PACKETS_PER_MEASUREMENT {
+            let sent_at = send_base + Self::PACKET_SEND_INTERVAL * i;
+            let jitter_ms = (now.wrapping_add(i as u64) % 20) as f64 / 10.0;
+            let one_way_delay = Duration::from_micros(((base_delay_ms + jitter_ms) * 1000.0) as u64);
+            let arrived_at = sent_at + one_way_delay;
+            self.last_overuse_signal = self.gcc_estimator.on_packet(PacketTiming {
+                sent_at,
+                arrived_at,
+                size_bytes: Self::PACKET_SIZE_BYTES,
+            });
+
+            let seq = self.next_transport_seq;
+            self.next_transport_seq = self.next_transport_seq.wrapping_add(1);
+            feedback_packets.push((seq, sent_at, arrived_at));
+        }
+        self.last_transport_feedback = Some(TransportFeedback { packets: feedback_packets });
+
+        let measured_bytes_per_sec = self.gcc_estimator.bandwidth_estimate_bytes_per_sec();
+        let bandwidth = if measured_bytes_per_sec > 0.0 {
+            measured_bytes_per_sec * 8.0 / 1000.0 // bytes/s -> Kbps
+        } else {
+            5000.0 // no completed sample yet on the very first cycle
+        };
         conditions.push(NetworkCondition {
             name: "bandwidth".to_string(),
             value: self.normalize_bandwidth(bandwidth),
             timestamp: now,
         });
+
+        // Drive the AIMD rate controller from the same overuse signal and measured throughput,
+        // so the target rate tracks real congestion instead of the wall-clock protocol timer
+        let expected_packet_size_bits = Self::PACKET_SIZE_BYTES as f64 * 8.0;
+        self.rate_controller.update(
+            self.last_overuse_signal,
+            measured_bytes_per_sec * 8.0,
+            expected_packet_size_bits,
+            Instant::now(),
+        );
         
         // Packet loss measurement - simulate varying packet loss
         let packet_loss = (now % 5) as f64;
@@ -166,8 +295,14 @@ impl DynamicProtocolPoc {
             timestamp: now,
         });
         
-        // Jitter measurement - simulate varying jitter
-        let jitter = 5.0 + (now % 20) as f64 / 10.0;
+        // Jitter measurement - the spread of this cycle's inter-packet delay variation, computed
+        // from the same transport feedback the bandwidth measurement just captured, rather than
+        // a synthetic `now % 20`
+        let jitter = self
+            .last_transport_feedback
+            .as_ref()
+            .map(TransportFeedback::jitter_spread_ms)
+            .unwrap_or(0.0);
         conditions.push(NetworkCondition {
             name: "jitter".to_string(),
             value: self.normalize_jitter(jitter),
@@ -203,9 +338,8 @@ impl DynamicProtocolPoc {
         println!("Running Dynamic Protocol POC for {} seconds...", duration_secs);
         
         let start_time = Instant::now();
-        let mut last_protocol_update = Instant::now();
-        let protocol_update_interval = Duration::from_secs(5); // Update protocol every 5 seconds
-        
+        let mut last_rate_control_state = self.rate_controller.state();
+
         // Run a baseline measurement with no protocol adaptation
         println!("Running baseline measurement (no protocol adaptation)...");
         self.set_adaptation(false);
@@ -257,8 +391,17 @@ impl DynamicProtocolPoc {
                 println!("Measured {}: {:.2}", condition.name, condition.value);
             }
             
-            // If adaptation is enabled and it's time to update the protocol
-            if self.adaptation_enabled && last_protocol_update.elapsed() >= protocol_update_interval {
+            // Trigger protocol regeneration from the AIMD controller's congestion signal rather
+            // than a wall-clock tick: either it just cut the rate (overuse), or the target rate
+            // has drifted far enough from what we last adapted to that it's worth reacting to
+            let rate_control_state = self.rate_controller.state();
+            let current_rate_bps = self.rate_controller.rate_bps();
+            let transitioned_to_decrease =
+                rate_control_state == RateControlState::Decrease && last_rate_control_state != RateControlState::Decrease;
+            let crossed_rate_delta = (current_rate_bps - self.last_triggered_rate_bps).abs() >= self.rate_change_delta_bps;
+            last_rate_control_state = rate_control_state;
+
+            if self.adaptation_enabled && (transitioned_to_decrease || crossed_rate_delta) {
                 println!("\nAttempting to generate a new protocol based on current conditions...");
                 
                 // Try generating a new protocol
@@ -288,8 +431,8 @@ impl DynamicProtocolPoc {
                     println!("Protocol deployed successfully");
                     self.active_protocol = Some(protocol);
                 }
-                
-                last_protocol_update = Instant::now();
+
+                self.last_triggered_rate_bps = current_rate_bps;
             }
             
             // Run a performance measurement cycle
@@ -397,6 +540,7 @@ impl DynamicProtocolPoc {
             jitter,
             transfer_time,
             protocol_id: self.active_protocol.as_ref().map(|p| p.id.clone()),
+            transport_feedback: self.last_transport_feedback.clone(),
         }
     }
     
@@ -497,10 +641,63 @@ impl DynamicProtocolPoc {
         }
         
         println!("\nResults saved to {}", filename);
-        
+
+        self.save_transport_feedback(&results)?;
+
         Ok(())
     }
-    
+
+    /// Save per-packet transport-wide feedback (sequence number, send/receive timestamps and
+    /// inter-arrival delta) to a second CSV alongside the performance summary, enabling offline
+    /// reconstruction of the congestion-control decisions taken from this run
+    fn save_transport_feedback(&self, results: &[PerformanceResult]) -> Result<(), String> {
+        let filename = format!("dynamic_protocol_transport_feedback_{}.csv",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_else(|_| Duration::from_secs(0))
+                .as_secs()
+        );
+
+        let mut file = match File::create(&filename) {
+            Ok(file) => file,
+            Err(e) => return Err(format!("Failed to create transport feedback file: {}", e)),
+        };
+
+        if let Err(e) = writeln!(file, "cycle_timestamp,seq,send_ts_ms,recv_ts_ms,inter_arrival_delta_ms") {
+            return Err(format!("Failed to write to transport feedback file: {}", e));
+        }
+
+        for result in results {
+            let feedback = match &result.transport_feedback {
+                Some(feedback) => feedback,
+                None => continue,
+            };
+            let base_sent_at = match feedback.packets.first() {
+                Some(&(_, sent_at, _)) => sent_at,
+                None => continue,
+            };
+
+            let mut prev_arrived_at = None;
+            for &(seq, sent_at, arrived_at) in &feedback.packets {
+                let send_ts_ms = result.timestamp * 1000 + sent_at.duration_since(base_sent_at).as_millis() as u64;
+                let recv_ts_ms = result.timestamp * 1000 + arrived_at.duration_since(base_sent_at).as_millis() as u64;
+                let inter_arrival_delta_ms = prev_arrived_at
+                    .map(|prev| arrived_at.duration_since(prev).as_secs_f64() * 1000.0)
+                    .unwrap_or(0.0);
+                prev_arrived_at = Some(arrived_at);
+
+                if let Err(e) = writeln!(file, "{},{},{},{},{:.3}",
+                    result.timestamp, seq, send_ts_ms, recv_ts_ms, inter_arrival_delta_ms) {
+                    return Err(format!("Failed to write to transport feedback file: {}", e));
+                }
+            }
+        }
+
+        println!("Transport-wide feedback saved to {}", filename);
+
+        Ok(())
+    }
+
     /// Normalize latency value to 0-1 scale (lower is better)
     fn normalize_latency(&self, latency_ms: f64) -> f64 {
         // Clamp to reasonable range
@@ -563,45 +760,37 @@ impl DynamicProtocolPoc {
     }
 }
 
-/// Quick demo of the Dynamic Protocol POC
-fn run_quick_demo() -> Result<(), String> {
-    println!("Running Quick Demo of Dynamic Protocol Adaptation...");
-    
-    // Create and initialize the POC
-    let mut poc = DynamicProtocolPoc::new();
-    poc.initialize()?;
-    
-    // Run the POC for 30 seconds
-    poc.run(30)?;
-    
-    Ok(())
-}
+/// Directory of shipped [`DemoConfig`] scenario files, enumerated by `main` when no
+/// `--scenario` flag is given
+const SCENARIOS_DIR: &str = "scenarios";
 
-/// Run comprehensive large-scale simulation
-fn run_large_scale_simulation() -> Result<(), String> {
-    println!("Running Comprehensive Large-Scale Network Simulation...");
-    
-    // Create and initialize large-scale simulator
-    let mut simulator = LargeScaleSimulator::new();
-    
-    // Configure simulation
-    let config = SimulationConfig {
-        node_count: 100,
-        connection_density: 0.2,
-        duration_secs: 120,
-        enable_live_visualization: false,
-    };
-    
-    // Initialize simulator
-    simulator.initialize(&config).map_err(|e| e.0)?;
-    
-    // Run simulation
-    simulator.run(config.duration_secs).map_err(|e| e.0)?;
-    
-    println!("Simulation completed successfully!");
-    println!("Please check the generated reports and visualizations for detailed results.");
-    
-    Ok(())
+/// Run a single loaded [`DemoConfig`], dispatching to the plain POC or the large-scale
+/// simulator depending on its variant
+fn run_demo(demo: &DemoConfig) -> Result<(), String> {
+    println!("Running {}...", demo.name());
+
+    match demo {
+        DemoConfig::QuickDemo { duration_secs, .. } => {
+            let mut poc = DynamicProtocolPoc::new();
+            poc.initialize()?;
+            poc.run(*duration_secs)?;
+            Ok(())
+        }
+        DemoConfig::LargeScale { simulation, .. } => {
+            if simulation.check_deterministic {
+                return LargeScaleSimulator::check_deterministic(simulation, 200)
+                    .map_err(|e| e.0);
+            }
+
+            let mut simulator = LargeScaleSimulator::new();
+            simulator.initialize(simulation).map_err(|e| e.0)?;
+            simulator.run(simulation.duration_secs).map_err(|e| e.0)?;
+
+            println!("Simulation completed successfully!");
+            println!("Please check the generated reports and visualizations for detailed results.");
+            Ok(())
+        }
+    }
 }
 
 fn main() -> Result<(), String> {
@@ -613,16 +802,40 @@ fn main() -> Result<(), String> {
     println!("in both simple and complex network environments. It demonstrates how");
     println!("protocols adapt to changing network conditions for optimal performance.");
     println!();
-    
-    // First prompt the user to select demo type
-    println!("Select demonstration type:");
-    println!("1. Quick Demo (30 seconds)");
-    println!("2. Comprehensive Large-Scale Simulation (2 minutes)");
-    println!("3. Extreme Network Conditions Stress Test (3 minutes)");
+
+    // `--scenario <path>` loads one demo config directly, bypassing the enumerated menu
+    let mut args = std::env::args().skip(1);
+    if let Some(flag) = args.next() {
+        if flag == "--scenario" {
+            let path = args
+                .next()
+                .ok_or_else(|| "--scenario requires a path argument".to_string())?;
+            let demo = DemoConfig::from_json_file(&path).map_err(|e| e.0)?;
+            return run_demo(&demo);
+        }
+        return Err(format!("Unrecognized argument: {}", flag));
+    }
+
+    // No flag given: enumerate the shipped scenario files and run the first one
+    println!("Select demonstration type (pass --scenario <path> to pick one directly):");
+    let scenario_paths = DemoConfig::list_dir(SCENARIOS_DIR).map_err(|e| e.0)?;
+    if scenario_paths.is_empty() {
+        return Err(format!("No scenario files found under {}", SCENARIOS_DIR));
+    }
+
+    let demos: Vec<DemoConfig> = scenario_paths
+        .iter()
+        .map(DemoConfig::from_json_file)
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.0)?;
+
+    for (i, demo) in demos.iter().enumerate() {
+        println!("{}. {} ({})", i + 1, demo.name(), demo.description());
+    }
     println!();
-    
-    // For now, default to the comprehensive simulation
-    run_large_scale_simulation()?;
-    
+
+    // For now, default to the first enumerated scenario
+    run_demo(&demos[0])?;
+
     Ok(())
 }
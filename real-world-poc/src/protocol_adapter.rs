@@ -1,10 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
 use std::io;
 
 use unrealnet_core::dynphys::generator::{GeneratedProtocol, FlowControlParameters};
 use unrealnet_core::dynphys::generator::{SecurityParameters, RoutingParameters};
 
+mod backend;
+mod clock;
+mod congestion;
+mod fault;
+mod filter;
+mod pcap;
+pub use backend::{InterfaceBackend, LinuxBackend, SimulatedBackend};
+pub use clock::{Clock, Duration, Instant, ManualClock, SystemClock};
+pub use congestion::{Bbr, CongestionController, Cubic, MSS_BYTES};
+pub use fault::{FaultInjector, FaultInjectorConfig, FaultOutcome};
+pub use filter::{Action, Hook, HookPoint, PacketMeta, Routine, Rule, Verdict};
+pub use pcap::{LinkType, PcapMode, PcapWriter};
+
 /// Network interface type
 #[derive(Debug, Clone, PartialEq)]
 pub enum NetworkInterfaceType {
@@ -62,6 +76,29 @@ pub struct RealProtocolAdapter {
     active_protocol: Option<GeneratedProtocol>,
     /// Protocol deployment stats
     stats: Arc<Mutex<ProtocolStats>>,
+    /// Packet-filter hooks installed by [`Self::deploy_protocol`], keyed by hook point
+    hooks: HashMap<HookPoint, Hook>,
+    /// Optional fault-injection wrapper degrading traffic before [`Self::collect_stats`]
+    /// measures it
+    fault_injector: Option<FaultInjector>,
+    /// Pcap writers capturing traffic on a given interface, keyed by interface name
+    pcap_writers: HashMap<String, PcapWriter>,
+    /// Backend discovery/stats/flow-control is actually driven through; [`SimulatedBackend`]
+    /// for the POC demo and tests, [`LinuxBackend`] on a real machine
+    backend: Box<dyn InterfaceBackend>,
+    /// Optional congestion controller run in a feedback loop against live interface stats;
+    /// when set, it (not the hard-coded constants) drives [`Self::collect_stats`]'s reported
+    /// bandwidth and throughput
+    congestion_controller: Option<Box<dyn CongestionController>>,
+    /// Total rx bytes observed across interfaces as of the last [`Self::collect_stats`] call,
+    /// used to turn the next call's delta into an ack for the congestion controller
+    last_congestion_rx_bytes: u64,
+    /// Source of [`Instant`]s for deployment timestamps and stat sampling; [`SystemClock`] by
+    /// default, swappable for a [`ManualClock`] in tests
+    clock: Box<dyn Clock>,
+    /// Rolling time-series of `(capture instant, stats)` samples per interface, keyed by
+    /// interface name, that [`Self::collect_stats_over`] derives rate deltas from
+    stat_history: HashMap<String, VecDeque<(Instant, InterfaceStats)>>,
 }
 
 /// Protocol deployment statistics
@@ -69,8 +106,8 @@ pub struct RealProtocolAdapter {
 pub struct ProtocolStats {
     /// Number of deployments
     pub deployments: u32,
-    /// Last deployment timestamp
-    pub last_deployment: Option<u64>,
+    /// Instant the protocol was last deployed
+    pub last_deployment: Option<Instant>,
     /// Current bandwidth usage (Kbps)
     pub current_bandwidth: f64,
     /// Current packet throughput (packets/s)
@@ -79,70 +116,89 @@ pub struct ProtocolStats {
     pub current_latency: f64,
     /// Current packet loss rate (%)
     pub current_packet_loss: f64,
+    /// Hit counts for every installed filter rule, keyed by `"<hook>/<routine>/<rule>"`
+    pub rule_hits: HashMap<String, u64>,
 }
 
 impl RealProtocolAdapter {
-    /// Create a new protocol adapter
-    pub fn new(name: &str) -> Self {
+    /// Create a new protocol adapter driven by `backend` (e.g. [`SimulatedBackend`] or
+    /// [`LinuxBackend`])
+    pub fn new(name: &str, backend: Box<dyn InterfaceBackend>) -> Self {
         Self {
             name: name.to_string(),
             interfaces: HashMap::new(),
             active_protocol: None,
             stats: Arc::new(Mutex::new(ProtocolStats::default())),
+            hooks: HashMap::new(),
+            fault_injector: None,
+            pcap_writers: HashMap::new(),
+            backend,
+            congestion_controller: None,
+            last_congestion_rx_bytes: 0,
+            clock: Box::new(SystemClock::new()),
+            stat_history: HashMap::new(),
+        }
+    }
+
+    /// Create a new protocol adapter backed by [`SimulatedBackend`], the POC default
+    pub fn new_simulated(name: &str) -> Self {
+        Self::new(name, Box::new(SimulatedBackend::new()))
+    }
+
+    /// Wrap the datapath in a [`FaultInjector`] so subsequent [`Self::collect_stats`] calls
+    /// reflect degraded (dropped/corrupted/rate-limited) traffic instead of the idealized path
+    pub fn set_fault_injector(&mut self, injector: FaultInjector) {
+        self.fault_injector = Some(injector);
+    }
+
+    /// Drive the adapter's flow-control parameters from `controller`'s feedback loop instead
+    /// of the fixed bandwidth/throughput constants
+    pub fn set_congestion_controller(&mut self, controller: Box<dyn CongestionController>) {
+        self.congestion_controller = Some(controller);
+        self.last_congestion_rx_bytes = 0;
+    }
+
+    /// Drive deployment timestamps and stat sampling from `clock` instead of the system clock,
+    /// so [`Self::collect_stats_over`]'s rate deltas can be tested deterministically
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Attach a pcap capture file to the named interface, so traffic observed on it while a
+    /// protocol is active is written in standard pcap format for offline analysis in
+    /// `tcpdump`/Wireshark
+    pub fn attach_pcap(&mut self, interface_name: &str, writer: PcapWriter) {
+        self.pcap_writers.insert(interface_name.to_string(), writer);
+    }
+
+    /// Record a frame observed on `interface_name` into its attached pcap writer, if any
+    pub fn capture_rx(&mut self, interface_name: &str, frame: &[u8]) -> io::Result<()> {
+        match self.pcap_writers.get_mut(interface_name) {
+            Some(writer) => writer.write_rx(frame),
+            None => Ok(()),
+        }
+    }
+
+    /// Record a frame transmitted on `interface_name` into its attached pcap writer, if any
+    pub fn capture_tx(&mut self, interface_name: &str, frame: &[u8]) -> io::Result<()> {
+        match self.pcap_writers.get_mut(interface_name) {
+            Some(writer) => writer.write_tx(frame),
+            None => Ok(()),
         }
     }
     
-    /// Discover network interfaces
+    /// Discover network interfaces through the configured [`InterfaceBackend`]
     pub fn discover_interfaces(&mut self) -> Result<Vec<String>, io::Error> {
-        // For the POC, we'll simulate interface discovery instead of actually reading from the system
-        println!("Simulating interface discovery...");
-        
-        // Create some simulated interfaces
-        let interfaces = vec![
-            ("eth0", NetworkInterfaceType::Ethernet),
-            ("wlan0", NetworkInterfaceType::Wireless),
-            ("lo", NetworkInterfaceType::Loopback),
-            ("docker0", NetworkInterfaceType::Virtual),
-        ];
-        
-        // Clear existing interfaces
+        let interfaces = self.backend.discover()?;
+
         self.interfaces.clear();
-        
         let mut interface_names = Vec::new();
-        
-        // Create simulated interface objects
-        for (name, if_type) in interfaces {
-            let stats = InterfaceStats {
-                rx_bytes: 1_000_000,
-                tx_bytes: 500_000,
-                rx_packets: 10_000,
-                tx_packets: 5_000,
-                rx_errors: 10,
-                tx_errors: 5,
-                rx_dropped: 20,
-                tx_dropped: 10,
-            };
-            
-            let interface = NetworkInterface {
-                name: name.to_string(),
-                interface_type: if_type.clone(),
-                active: true,
-                stats,
-            };
-            
-            self.interfaces.insert(name.to_string(), interface);
-            interface_names.push(name.to_string());
-            
-            println!("  Found interface: {} ({})", name, 
-                match if_type {
-                    NetworkInterfaceType::Ethernet => "Ethernet",
-                    NetworkInterfaceType::Wireless => "Wireless",
-                    NetworkInterfaceType::Virtual => "Virtual",
-                    NetworkInterfaceType::Loopback => "Loopback",
-                }
-            );
+
+        for interface in interfaces {
+            interface_names.push(interface.name.clone());
+            self.interfaces.insert(interface.name.clone(), interface);
         }
-        
+
         Ok(interface_names)
     }
     
@@ -165,13 +221,13 @@ impl RealProtocolAdapter {
         
         // Apply security parameters
         self.apply_security_parameters(&protocol.security)?;
-        
+
+        // Install the packet-filter hooks derived from the protocol's security parameters
+        self.install_filter_hooks(&protocol.security);
+
         // Update deployment stats
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_else(|_| std::time::Duration::from_secs(0))
-            .as_secs();
-            
+        let now = self.clock.now();
+
         if let Ok(mut stats) = self.stats.lock() {
             stats.deployments += 1;
             stats.last_deployment = Some(now);
@@ -180,21 +236,15 @@ impl RealProtocolAdapter {
         Ok(true)
     }
     
-    /// Apply flow control parameters to network interfaces
+    /// Apply flow control parameters to every discovered interface through the configured
+    /// [`InterfaceBackend`]
     fn apply_flow_control(&self, flow_control: &FlowControlParameters) -> Result<(), String> {
-        // For our POC, we'll simulate this instead of actually configuring the network
-        println!("Simulating flow control application:");
-        println!("  Max packets/sec: {}", flow_control.max_packets_per_second);
-        println!("  Window size: {}", flow_control.window_size);
-        println!("  Congestion scaling: {}", flow_control.congestion_scaling);
-        println!("  Backpressure threshold: {}", flow_control.backpressure_threshold);
-        
-        // Here we would normally run tc commands:
-        // tc qdisc add dev eth0 root handle 1: htb default 10
-        // tc class add dev eth0 parent 1: classid 1:10 htb rate XXkbit ceil YYkbit
-        
+        for name in self.interfaces.keys() {
+            self.backend.apply_flow_control(name, flow_control)?;
+        }
+
         println!("Flow control applied to interfaces successfully");
-        
+
         Ok(())
     }
     
@@ -224,10 +274,40 @@ impl RealProtocolAdapter {
         Ok(())
     }
     
+    /// Re-read every discovered interface's counters through the configured
+    /// [`InterfaceBackend`], recording each as a timestamped sample for [`Self::collect_stats_over`]
+    pub fn refresh_interface_stats(&mut self) -> io::Result<()> {
+        for (name, interface) in self.interfaces.iter_mut() {
+            interface.stats = self.backend.read_stats(name)?;
+        }
+        self.record_stat_samples();
+        Ok(())
+    }
+
+    /// Maximum number of historical samples retained per interface; older samples are dropped
+    /// once a newer one pushes the history past this length
+    const STAT_HISTORY_CAPACITY: usize = 32;
+
+    /// Append the current reading for every interface to its rolling history, trimming to
+    /// [`Self::STAT_HISTORY_CAPACITY`]
+    fn record_stat_samples(&mut self) {
+        let now = self.clock.now();
+        for (name, interface) in &self.interfaces {
+            let history = self.stat_history.entry(name.clone()).or_default();
+            history.push_back((now, interface.stats.clone()));
+            while history.len() > Self::STAT_HISTORY_CAPACITY {
+                history.pop_front();
+            }
+        }
+    }
+
     /// Collect current performance statistics
     pub fn collect_stats(&mut self) -> ProtocolStats {
         let mut current_stats = ProtocolStats::default();
-        
+        let _ = self.refresh_interface_stats();
+        let (fault_loss_pct, fault_latency_ms) = self.sample_fault_impact();
+        let congestion_feedback = self.run_congestion_feedback();
+
         // Simulate collecting stats for a demonstration
         if let Ok(mut stats) = self.stats.lock() {
             // Simulate real-time metric improvements with the protocol deployed
@@ -243,12 +323,207 @@ impl RealProtocolAdapter {
                 stats.current_latency = 25.0; // 25 ms
                 stats.current_packet_loss = 1.2; // 1.2%
             }
-            
+
+            // A live congestion controller's steady-state window replaces the fixed
+            // bandwidth/throughput numbers above rather than adding to them
+            if let Some((bandwidth_kbps, throughput_pps)) = congestion_feedback {
+                stats.current_bandwidth = bandwidth_kbps;
+                stats.current_throughput = throughput_pps;
+            }
+
+            stats.current_packet_loss = (stats.current_packet_loss + fault_loss_pct).min(100.0);
+            stats.current_latency += fault_latency_ms;
+
             current_stats = stats.clone();
         }
-        
+
+        current_stats.rule_hits = self.rule_hit_counts();
+
         current_stats
     }
+
+    /// Like [`Self::collect_stats`], but `current_bandwidth`/`current_throughput` are computed
+    /// as real `Δrx_bytes/Δt` and `Δpackets/Δt` rate deltas over the last `window` of recorded
+    /// samples instead of the fixed placeholders used when no [`CongestionController`] is set
+    pub fn collect_stats_over(&mut self, window: Duration) -> ProtocolStats {
+        let _ = self.refresh_interface_stats();
+        let (fault_loss_pct, fault_latency_ms) = self.sample_fault_impact();
+        let congestion_feedback = self.run_congestion_feedback();
+        let (bandwidth_kbps, throughput_pps) = self.rate_deltas_over(window);
+
+        let mut current_stats = self.stats.lock().map(|stats| stats.clone()).unwrap_or_default();
+
+        current_stats.current_bandwidth = bandwidth_kbps;
+        current_stats.current_throughput = throughput_pps;
+
+        // A live congestion controller's steady-state window takes priority over the measured
+        // rate deltas, same as in collect_stats
+        if let Some((bandwidth_kbps, throughput_pps)) = congestion_feedback {
+            current_stats.current_bandwidth = bandwidth_kbps;
+            current_stats.current_throughput = throughput_pps;
+        }
+
+        current_stats.current_packet_loss = (current_stats.current_packet_loss + fault_loss_pct).min(100.0);
+        current_stats.current_latency += fault_latency_ms;
+        current_stats.rule_hits = self.rule_hit_counts();
+
+        current_stats
+    }
+
+    /// Sum `Δrx_bytes/Δt` (as kbps) and `Δrx_packets/Δt` (as pps) across every interface's
+    /// samples falling within the last `window`, using the oldest and newest in-window sample
+    /// as the delta's endpoints
+    fn rate_deltas_over(&self, window: Duration) -> (f64, f64) {
+        let now = self.clock.now();
+        let mut bandwidth_kbps = 0.0;
+        let mut throughput_pps = 0.0;
+
+        for history in self.stat_history.values() {
+            let in_window: Vec<_> = history.iter().filter(|(t, _)| now.duration_since(*t) <= window).collect();
+            let (Some(oldest), Some(newest)) = (in_window.first(), in_window.last()) else {
+                continue;
+            };
+
+            let elapsed = newest.0.duration_since(oldest.0);
+            if elapsed == Duration::ZERO {
+                continue;
+            }
+
+            let dt = elapsed.as_secs_f64();
+            let drx_bytes = newest.1.rx_bytes.saturating_sub(oldest.1.rx_bytes);
+            let drx_packets = newest.1.rx_packets.saturating_sub(oldest.1.rx_packets);
+            bandwidth_kbps += drx_bytes as f64 * 8.0 / 1000.0 / dt;
+            throughput_pps += drx_packets as f64 / dt;
+        }
+
+        (bandwidth_kbps, throughput_pps)
+    }
+
+    /// Assumed RTT used to turn the congestion controller's window into a rate; a real
+    /// implementation would measure this per interface instead
+    const CONGESTION_PROBE_RTT: StdDuration = StdDuration::from_millis(20);
+
+    /// Feed the installed [`CongestionController`], if any, an ack for the rx bytes observed
+    /// since the last call, and return `(bandwidth_kbps, throughput_pps)` derived from its
+    /// resulting window
+    fn run_congestion_feedback(&mut self) -> Option<(f64, f64)> {
+        let total_rx_bytes: u64 = self.interfaces.values().map(|iface| iface.stats.rx_bytes).sum();
+        let delta = total_rx_bytes.saturating_sub(self.last_congestion_rx_bytes);
+        self.last_congestion_rx_bytes = total_rx_bytes;
+
+        let controller = self.congestion_controller.as_mut()?;
+        if delta > 0 {
+            controller.on_ack(delta, Self::CONGESTION_PROBE_RTT);
+        }
+
+        let window_bytes = controller.window() as f64;
+        let rtt_secs = Self::CONGESTION_PROBE_RTT.as_secs_f64();
+        let bandwidth_kbps = window_bytes * 8.0 / 1000.0 / rtt_secs;
+        let throughput_pps = window_bytes / MSS_BYTES as f64 / rtt_secs;
+        Some((bandwidth_kbps, throughput_pps))
+    }
+
+    /// Probe size used to sample the fault injector's effect on loss/latency; arbitrary but
+    /// representative of a typical Ethernet frame
+    const FAULT_PROBE_SIZE: usize = 1500;
+    /// Number of probe packets sampled per [`Self::collect_stats`] call
+    const FAULT_SAMPLE_COUNT: u64 = 1000;
+
+    /// Run a batch of probe packets through the installed [`FaultInjector`], if any, and
+    /// return `(extra_packet_loss_pct, extra_latency_ms)` to fold into [`ProtocolStats`]
+    fn sample_fault_impact(&mut self) -> (f64, f64) {
+        let Some(injector) = self.fault_injector.as_mut() else {
+            return (0.0, 0.0);
+        };
+
+        let mut dropped = 0u64;
+        let mut corrupted = 0u64;
+        for _ in 0..Self::FAULT_SAMPLE_COUNT {
+            let mut probe = vec![0u8; Self::FAULT_PROBE_SIZE];
+            match injector.inject_tx(&mut probe) {
+                FaultOutcome::Dropped | FaultOutcome::RateLimited | FaultOutcome::Held => dropped += 1,
+                FaultOutcome::Passed | FaultOutcome::Reordered => {
+                    if probe.iter().any(|&b| b != 0) {
+                        corrupted += 1;
+                    }
+                }
+            }
+        }
+
+        let loss_pct = dropped as f64 / Self::FAULT_SAMPLE_COUNT as f64 * 100.0;
+        // Each corrupted packet costs roughly one retransmission, i.e. an extra one-way trip
+        let latency_penalty_ms = corrupted as f64 / Self::FAULT_SAMPLE_COUNT as f64 * BASE_RETRANSMIT_PENALTY_MS;
+        (loss_pct, latency_penalty_ms)
+    }
+}
+
+/// Approximate extra one-way delay a corrupted-and-retransmitted packet adds, used to turn the
+/// fault injector's corruption rate into a latency penalty
+const BASE_RETRANSMIT_PENALTY_MS: f64 = 20.0;
+
+/// Verification threshold at or above which [`RealProtocolAdapter::deploy_protocol`] installs a
+/// default-drop ingress/egress policy instead of default-accept (nftables-style base-chain
+/// policy)
+const HIGH_VERIFICATION_THRESHOLD: f64 = 0.8;
+
+impl RealProtocolAdapter {
+    /// Build and install the Ingress/Egress/LocalIngress/LocalEgress hooks for the deployed
+    /// protocol's security posture: a high `verification_threshold` switches ingress/egress to
+    /// a default-drop policy with an explicit loopback allowance, while local traffic is
+    /// always accepted by default.
+    fn install_filter_hooks(&mut self, security: &SecurityParameters) {
+        let edge_default = if security.verification_threshold >= HIGH_VERIFICATION_THRESHOLD {
+            Verdict::Drop
+        } else {
+            Verdict::Accept
+        };
+
+        let mut ingress_loopback = Routine::new("loopback");
+        ingress_loopback.add_rule(Rule::new("allow-loopback", Action::Accept, |_, iface| {
+            iface.interface_type == NetworkInterfaceType::Loopback
+        }));
+        let mut ingress = Hook::new(HookPoint::Ingress, edge_default);
+        ingress.add_routine(ingress_loopback);
+        self.hooks.insert(HookPoint::Ingress, ingress);
+
+        let mut egress_loopback = Routine::new("loopback");
+        egress_loopback.add_rule(Rule::new("allow-loopback", Action::Accept, |_, iface| {
+            iface.interface_type == NetworkInterfaceType::Loopback
+        }));
+        let mut egress = Hook::new(HookPoint::Egress, edge_default);
+        egress.add_routine(egress_loopback);
+        self.hooks.insert(HookPoint::Egress, egress);
+
+        self.hooks.insert(HookPoint::LocalIngress, Hook::new(HookPoint::LocalIngress, Verdict::Accept));
+        self.hooks.insert(HookPoint::LocalEgress, Hook::new(HookPoint::LocalEgress, Verdict::Accept));
+    }
+
+    /// Evaluate the installed hook for `point` against a packet observed on `iface_name`,
+    /// defaulting to [`Verdict::Accept`] when no hook has been installed yet or the interface
+    /// is unknown
+    pub fn filter_packet(&self, point: HookPoint, pkt: &PacketMeta, iface_name: &str) -> Verdict {
+        let iface = match self.interfaces.get(iface_name) {
+            Some(iface) => iface,
+            None => return Verdict::Accept,
+        };
+        match self.hooks.get(&point) {
+            Some(hook) => hook.evaluate(pkt, iface),
+            None => Verdict::Accept,
+        }
+    }
+
+    /// Per-rule hit counts across every installed hook, keyed by `"<hook>/<routine>/<rule>"`
+    pub fn rule_hit_counts(&self) -> HashMap<String, u64> {
+        let mut counts = HashMap::new();
+        for (point, hook) in &self.hooks {
+            for routine in hook.routines() {
+                for rule in routine.rules() {
+                    counts.insert(format!("{:?}/{}/{}", point, routine.name, rule.name()), rule.hit_count());
+                }
+            }
+        }
+        counts
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,246 @@
+//! Pluggable interface backends: `discover_interfaces`, `read_stats`, and `apply_flow_control`
+//! used to run entirely in simulation. [`InterfaceBackend`] lets [`RealProtocolAdapter`] swap
+//! the simulated path for a [`LinuxBackend`] that reads real interfaces from `/proc/net/dev`
+//! and `/sys/class/net` and drives them with `tc`/`netem`, while keeping simulation available
+//! for tests.
+//!
+//! [`RealProtocolAdapter`]: super::RealProtocolAdapter
+
+use std::fs;
+use std::io;
+use std::process::Command;
+
+use unrealnet_core::dynphys::generator::FlowControlParameters;
+
+use super::{NetworkInterface, NetworkInterfaceType, InterfaceStats};
+
+/// Source of truth for interface discovery, stats, and flow-control application that
+/// [`RealProtocolAdapter`] drives its datapath through.
+///
+/// [`RealProtocolAdapter`]: super::RealProtocolAdapter
+pub trait InterfaceBackend: Send {
+    /// Enumerate the interfaces this backend knows about
+    fn discover(&self) -> io::Result<Vec<NetworkInterface>>;
+    /// Read current counters for a single interface
+    fn read_stats(&self, name: &str) -> io::Result<InterfaceStats>;
+    /// Apply flow-control parameters (rate, window, congestion scaling) to an interface
+    fn apply_flow_control(&self, name: &str, params: &FlowControlParameters) -> Result<(), String>;
+}
+
+/// Backend that fabricates interfaces and stats instead of touching the system, used for the
+/// POC demo and for tests
+#[derive(Debug, Default)]
+pub struct SimulatedBackend;
+
+impl SimulatedBackend {
+    /// Create a new simulated backend
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl InterfaceBackend for SimulatedBackend {
+    fn discover(&self) -> io::Result<Vec<NetworkInterface>> {
+        println!("Simulating interface discovery...");
+
+        let interfaces = vec![
+            ("eth0", NetworkInterfaceType::Ethernet),
+            ("wlan0", NetworkInterfaceType::Wireless),
+            ("lo", NetworkInterfaceType::Loopback),
+            ("docker0", NetworkInterfaceType::Virtual),
+        ];
+
+        Ok(interfaces
+            .into_iter()
+            .map(|(name, interface_type)| {
+                println!(
+                    "  Found interface: {} ({:?})",
+                    name, interface_type
+                );
+                NetworkInterface {
+                    name: name.to_string(),
+                    interface_type,
+                    active: true,
+                    stats: InterfaceStats {
+                        rx_bytes: 1_000_000,
+                        tx_bytes: 500_000,
+                        rx_packets: 10_000,
+                        tx_packets: 5_000,
+                        rx_errors: 10,
+                        tx_errors: 5,
+                        rx_dropped: 20,
+                        tx_dropped: 10,
+                    },
+                }
+            })
+            .collect())
+    }
+
+    fn read_stats(&self, _name: &str) -> io::Result<InterfaceStats> {
+        Ok(InterfaceStats {
+            rx_bytes: 1_000_000,
+            tx_bytes: 500_000,
+            rx_packets: 10_000,
+            tx_packets: 5_000,
+            rx_errors: 10,
+            tx_errors: 5,
+            rx_dropped: 20,
+            tx_dropped: 10,
+        })
+    }
+
+    fn apply_flow_control(&self, name: &str, params: &FlowControlParameters) -> Result<(), String> {
+        println!("Simulating flow control application on {}:", name);
+        println!("  Max packets/sec: {}", params.max_packets_per_second);
+        println!("  Window size: {}", params.window_size);
+        println!("  Congestion scaling: {}", params.congestion_scaling);
+        println!("  Backpressure threshold: {}", params.backpressure_threshold);
+        Ok(())
+    }
+}
+
+/// ARPHRD_LOOPBACK, the `/sys/class/net/<if>/type` value for loopback devices
+const ARPHRD_LOOPBACK: u32 = 772;
+
+/// Backend that reads real interfaces and counters from the Linux `/proc` and `/sys`
+/// pseudo-filesystems and drives flow control with `tc qdisc`/`tc class htb` plus `netem`
+#[derive(Debug, Default)]
+pub struct LinuxBackend;
+
+impl LinuxBackend {
+    /// Create a new backend targeting the local machine's network stack
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn interface_type(name: &str) -> NetworkInterfaceType {
+        let sys_type = fs::read_to_string(format!("/sys/class/net/{}/type", name))
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok());
+
+        match sys_type {
+            Some(ARPHRD_LOOPBACK) => NetworkInterfaceType::Loopback,
+            _ if name.starts_with("wlan") || name.starts_with("wl") => NetworkInterfaceType::Wireless,
+            _ if name.starts_with("docker") || name.starts_with("veth") || name.starts_with("br") => {
+                NetworkInterfaceType::Virtual
+            }
+            _ => NetworkInterfaceType::Ethernet,
+        }
+    }
+
+    /// Parse one non-header line of `/proc/net/dev`:
+    /// `iface: rx_bytes rx_packets rx_errs rx_drop ... tx_bytes tx_packets tx_errs tx_drop ...`
+    fn parse_dev_line(line: &str) -> Option<(String, InterfaceStats)> {
+        let (name, rest) = line.split_once(':')?;
+        let fields: Vec<u64> = rest.split_whitespace().filter_map(|f| f.parse().ok()).collect();
+        if fields.len() < 12 {
+            return None;
+        }
+
+        Some((
+            name.trim().to_string(),
+            InterfaceStats {
+                rx_bytes: fields[0],
+                rx_packets: fields[1],
+                rx_errors: fields[2],
+                rx_dropped: fields[3],
+                tx_bytes: fields[8],
+                tx_packets: fields[9],
+                tx_errors: fields[10],
+                tx_dropped: fields[11],
+            },
+        ))
+    }
+}
+
+impl InterfaceBackend for LinuxBackend {
+    fn discover(&self) -> io::Result<Vec<NetworkInterface>> {
+        let dev = fs::read_to_string("/proc/net/dev")?;
+
+        Ok(dev
+            .lines()
+            .skip(2) // header lines
+            .filter_map(Self::parse_dev_line)
+            .map(|(name, stats)| NetworkInterface {
+                interface_type: Self::interface_type(&name),
+                active: true,
+                name,
+                stats,
+            })
+            .collect())
+    }
+
+    fn read_stats(&self, name: &str) -> io::Result<InterfaceStats> {
+        let dev = fs::read_to_string("/proc/net/dev")?;
+        dev.lines()
+            .skip(2)
+            .filter_map(Self::parse_dev_line)
+            .find(|(n, _)| n == name)
+            .map(|(_, stats)| stats)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no such interface: {}", name)))
+    }
+
+    fn apply_flow_control(&self, name: &str, params: &FlowControlParameters) -> Result<(), String> {
+        // Translate packets/sec into an approximate rate assuming a 1500-byte MTU, and the
+        // backpressure threshold into netem's loss percentage
+        const ASSUMED_PACKET_BYTES: u64 = 1500;
+        let rate_kbit = params.max_packets_per_second as u64 * ASSUMED_PACKET_BYTES * 8 / 1000;
+        let ceil_kbit = rate_kbit + (rate_kbit as f64 * params.congestion_scaling).round() as u64;
+
+        run_tc(&["qdisc", "replace", "dev", name, "root", "handle", "1:", "htb", "default", "10"])?;
+        run_tc(&[
+            "class", "replace", "dev", name, "parent", "1:", "classid", "1:10", "htb",
+            "rate", &format!("{}kbit", rate_kbit.max(1)),
+            "ceil", &format!("{}kbit", ceil_kbit.max(rate_kbit.max(1))),
+        ])?;
+        run_tc(&[
+            "qdisc", "replace", "dev", name, "parent", "1:10", "handle", "10:", "netem",
+            "limit", &params.window_size.to_string(),
+            "loss", &format!("{:.2}%", params.backpressure_threshold * 100.0),
+        ])?;
+
+        Ok(())
+    }
+}
+
+/// Run `tc <args>`, surfacing a non-zero exit or spawn failure as an error string
+fn run_tc(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("tc")
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run tc {}: {}", args.join(" "), e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "tc {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_proc_net_dev_line() {
+        let line = "  eth0: 1000 10 1 2 0 0 0 0 2000 20 3 4 0 0 0 0";
+        let (name, stats) = LinuxBackend::parse_dev_line(line).unwrap();
+
+        assert_eq!(name, "eth0");
+        assert_eq!(stats.rx_bytes, 1000);
+        assert_eq!(stats.rx_packets, 10);
+        assert_eq!(stats.tx_bytes, 2000);
+        assert_eq!(stats.tx_packets, 20);
+    }
+
+    #[test]
+    fn simulated_backend_reports_four_interfaces() {
+        let backend = SimulatedBackend::new();
+        let interfaces = backend.discover().unwrap();
+        assert_eq!(interfaces.len(), 4);
+    }
+}
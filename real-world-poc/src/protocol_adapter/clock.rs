@@ -0,0 +1,170 @@
+//! A small monotonic `Instant`/`Duration` abstraction, separate from `std::time`, so the
+//! fault injector's rate shaping and the adapter's stat sampling can be driven by an injectable
+//! [`Clock`] in tests instead of the wall clock.
+
+use std::ops::{Add, Sub};
+
+/// A duration of monotonic time, stored in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Duration {
+    nanos: u64,
+}
+
+impl Duration {
+    /// The zero duration
+    pub const ZERO: Duration = Duration { nanos: 0 };
+
+    /// Build a duration from a whole number of seconds
+    pub const fn from_secs(secs: u64) -> Self {
+        Self { nanos: secs.saturating_mul(1_000_000_000) }
+    }
+
+    /// Build a duration from a whole number of milliseconds
+    pub const fn from_millis(millis: u64) -> Self {
+        Self { nanos: millis.saturating_mul(1_000_000) }
+    }
+
+    /// Build a duration from a whole number of nanoseconds
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self { nanos }
+    }
+
+    /// This duration as fractional seconds
+    pub fn as_secs_f64(&self) -> f64 {
+        self.nanos as f64 / 1_000_000_000.0
+    }
+
+    /// This duration as whole milliseconds
+    pub fn as_millis(&self) -> u64 {
+        self.nanos / 1_000_000
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+    fn add(self, rhs: Duration) -> Duration {
+        Duration { nanos: self.nanos.saturating_add(rhs.nanos) }
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+    fn sub(self, rhs: Duration) -> Duration {
+        Duration { nanos: self.nanos.saturating_sub(rhs.nanos) }
+    }
+}
+
+/// A point in monotonic time, stored as nanoseconds since an arbitrary, clock-specific epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instant {
+    nanos_since_epoch: u64,
+}
+
+impl Instant {
+    /// Construct an instant directly from nanoseconds since the owning clock's epoch
+    pub const fn from_nanos_since_epoch(nanos_since_epoch: u64) -> Self {
+        Self { nanos_since_epoch }
+    }
+
+    /// Duration elapsed between `earlier` and `self`; zero if `earlier` is actually later
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        Duration::from_nanos(self.nanos_since_epoch.saturating_sub(earlier.nanos_since_epoch))
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+    fn add(self, rhs: Duration) -> Instant {
+        Instant { nanos_since_epoch: self.nanos_since_epoch.saturating_add(rhs.nanos) }
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+    fn sub(self, rhs: Duration) -> Instant {
+        Instant { nanos_since_epoch: self.nanos_since_epoch.saturating_sub(rhs.nanos) }
+    }
+}
+
+/// Source of the current [`Instant`], abstracted so tests can drive time deterministically
+/// instead of depending on the wall clock
+pub trait Clock: Send {
+    /// The current instant, monotonic within this clock's lifetime
+    fn now(&self) -> Instant;
+}
+
+/// [`Clock`] backed by `std::time::Instant`, anchored to the moment the [`SystemClock`] was
+/// created
+pub struct SystemClock {
+    epoch: std::time::Instant,
+}
+
+impl SystemClock {
+    /// Create a new clock anchored to the current moment
+    pub fn new() -> Self {
+        Self { epoch: std::time::Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::from_nanos_since_epoch(self.epoch.elapsed().as_nanos() as u64)
+    }
+}
+
+/// [`Clock`] that only advances when told to, for deterministic tests
+pub struct ManualClock {
+    now: std::cell::Cell<Instant>,
+}
+
+impl ManualClock {
+    /// Create a new manual clock starting at nanosecond zero
+    pub fn new() -> Self {
+        Self { now: std::cell::Cell::new(Instant::from_nanos_since_epoch(0)) }
+    }
+
+    /// Advance the clock by `duration`
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_since_is_monotonic() {
+        let earlier = Instant::from_nanos_since_epoch(1_000);
+        let later = Instant::from_nanos_since_epoch(1_500);
+        assert_eq!(later.duration_since(earlier), Duration::from_nanos(500));
+        assert_eq!(earlier.duration_since(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn manual_clock_only_advances_when_told() {
+        let clock = ManualClock::new();
+        let t0 = clock.now();
+        assert_eq!(clock.now(), t0);
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(clock.now().duration_since(t0), Duration::from_millis(10));
+    }
+}
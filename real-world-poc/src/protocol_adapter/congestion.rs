@@ -0,0 +1,194 @@
+//! Pluggable congestion-control models driving a deployed protocol's flow-control parameters.
+//! `FlowControlParameters` used to be applied once and never adapted; a [`CongestionController`]
+//! is instead run in a feedback loop against live [`InterfaceStats`](super::InterfaceStats) so
+//! `collect_stats` reports a controller's actual steady-state bandwidth/throughput.
+
+use std::time::Duration;
+
+/// Typical Ethernet MSS in bytes, used to convert between window (bytes) and segments
+pub const MSS_BYTES: u64 = 1460;
+
+/// Per-connection congestion window model driven by ack/loss/timeout feedback
+pub trait CongestionController: Send {
+    /// Record that `bytes` were newly acknowledged after `rtt`
+    fn on_ack(&mut self, bytes: u64, rtt: Duration);
+    /// Record a loss signal (e.g. a duplicate ack or an explicit drop notification)
+    fn on_loss(&mut self);
+    /// Record a retransmission timeout
+    fn on_timeout(&mut self);
+    /// Current congestion window, in bytes
+    fn window(&self) -> u64;
+}
+
+/// CUBIC congestion window: `W(t) = C*(t - K)^3 + W_max`, `K = cbrt(W_max*beta/C)`, with slow
+/// start before the first loss
+pub struct Cubic {
+    c: f64,
+    beta: f64,
+    w_max: f64,
+    cwnd: f64,
+    ss_thresh: f64,
+    in_slow_start: bool,
+    elapsed_since_loss: Duration,
+}
+
+impl Cubic {
+    /// CUBIC's standard scaling constant
+    const DEFAULT_C: f64 = 0.4;
+    /// CUBIC's standard multiplicative-decrease factor
+    const DEFAULT_BETA: f64 = 0.3;
+
+    /// Create a new CUBIC controller starting in slow start at one segment
+    pub fn new() -> Self {
+        Self {
+            c: Self::DEFAULT_C,
+            beta: Self::DEFAULT_BETA,
+            w_max: MSS_BYTES as f64,
+            cwnd: MSS_BYTES as f64,
+            ss_thresh: f64::MAX,
+            in_slow_start: true,
+            elapsed_since_loss: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for Cubic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CongestionController for Cubic {
+    fn on_ack(&mut self, bytes: u64, rtt: Duration) {
+        if self.in_slow_start {
+            self.cwnd += bytes as f64;
+            if self.cwnd >= self.ss_thresh {
+                self.in_slow_start = false;
+                self.elapsed_since_loss = Duration::ZERO;
+            }
+            return;
+        }
+
+        self.elapsed_since_loss += rtt;
+        let t = self.elapsed_since_loss.as_secs_f64();
+        let k = (self.w_max * self.beta / self.c).cbrt();
+        self.cwnd = (self.c * (t - k).powi(3) + self.w_max).max(MSS_BYTES as f64);
+    }
+
+    fn on_loss(&mut self) {
+        self.w_max = self.cwnd;
+        self.cwnd = (self.cwnd * (1.0 - self.beta)).max(MSS_BYTES as f64);
+        self.ss_thresh = self.cwnd;
+        self.in_slow_start = false;
+        self.elapsed_since_loss = Duration::ZERO;
+    }
+
+    fn on_timeout(&mut self) {
+        self.w_max = self.cwnd;
+        self.ss_thresh = (self.w_max * (1.0 - self.beta)).max(MSS_BYTES as f64);
+        self.cwnd = MSS_BYTES as f64;
+        self.in_slow_start = true;
+        self.elapsed_since_loss = Duration::ZERO;
+    }
+
+    fn window(&self) -> u64 {
+        self.cwnd as u64
+    }
+}
+
+/// Gain sequence BBR's ProbeBW phase cycles through: a brief probe-up, a compensating
+/// probe-down, then six cruise phases
+const BBR_PROBE_BW_GAIN_CYCLE: [f64; 8] = [1.25, 0.75, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+
+/// BBR congestion window: tracks a windowed max delivery rate and windowed min RTT, paces at
+/// `gain * bandwidth`, and caps inflight at `bandwidth * min_rtt`
+pub struct Bbr {
+    max_bandwidth_bytes_per_sec: f64,
+    min_rtt: Duration,
+    cycle_index: usize,
+    inflight_cap: u64,
+}
+
+impl Bbr {
+    /// Create a new BBR controller with no bandwidth/RTT samples yet
+    pub fn new() -> Self {
+        Self {
+            max_bandwidth_bytes_per_sec: 0.0,
+            min_rtt: Duration::MAX,
+            cycle_index: 0,
+            inflight_cap: MSS_BYTES,
+        }
+    }
+
+    /// Current pacing rate: `gain * bandwidth` for the active ProbeBW cycle phase
+    pub fn pacing_rate_bytes_per_sec(&self) -> f64 {
+        BBR_PROBE_BW_GAIN_CYCLE[self.cycle_index] * self.max_bandwidth_bytes_per_sec
+    }
+
+    /// Windowed min RTT observed so far
+    pub fn min_rtt(&self) -> Duration {
+        self.min_rtt
+    }
+}
+
+impl Default for Bbr {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CongestionController for Bbr {
+    fn on_ack(&mut self, bytes: u64, rtt: Duration) {
+        if rtt < self.min_rtt {
+            self.min_rtt = rtt;
+        }
+
+        let delivery_rate = bytes as f64 / rtt.as_secs_f64().max(1e-6);
+        if delivery_rate > self.max_bandwidth_bytes_per_sec {
+            self.max_bandwidth_bytes_per_sec = delivery_rate;
+        }
+
+        self.cycle_index = (self.cycle_index + 1) % BBR_PROBE_BW_GAIN_CYCLE.len();
+
+        let min_rtt_secs = if self.min_rtt == Duration::MAX { rtt.as_secs_f64() } else { self.min_rtt.as_secs_f64() };
+        self.inflight_cap = (self.max_bandwidth_bytes_per_sec * min_rtt_secs).max(MSS_BYTES as f64) as u64;
+    }
+
+    fn on_loss(&mut self) {
+        // BBR is delay-based: an isolated loss signal alone doesn't cut the bandwidth estimate
+    }
+
+    fn on_timeout(&mut self) {
+        self.max_bandwidth_bytes_per_sec *= 0.5;
+    }
+
+    fn window(&self) -> u64 {
+        self.inflight_cap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cubic_grows_in_slow_start_then_cuts_on_loss() {
+        let mut cubic = Cubic::new();
+        let before = cubic.window();
+        cubic.on_ack(MSS_BYTES, Duration::from_millis(50));
+        assert!(cubic.window() > before);
+
+        let pre_loss_window = cubic.window();
+        cubic.on_loss();
+        assert!(cubic.window() < pre_loss_window);
+    }
+
+    #[test]
+    fn bbr_caps_inflight_at_bandwidth_times_min_rtt() {
+        let mut bbr = Bbr::new();
+        bbr.on_ack(150_000, Duration::from_millis(100));
+        // ~1.5 Mbyte/s * 0.1s ~= 150_000 bytes, the single sample IS the bandwidth and min_rtt
+        assert!(bbr.window() <= 150_000);
+        assert!(bbr.window() >= MSS_BYTES);
+    }
+}
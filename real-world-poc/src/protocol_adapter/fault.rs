@@ -0,0 +1,240 @@
+//! Fault-injection wrapper for evaluating a deployed protocol under adverse conditions: drop,
+//! corrupt, truncate, and rate-limit packets on the adapter's datapath before `collect_stats`
+//! measures the result. Every drop/corrupt/reorder roll is derived from a seed plus a
+//! monotonically increasing packet counter (a tiny xorshift64 PRNG) rather than the global RNG,
+//! so a run with the same seed replays those rolls identically; rate-limit decisions depend on
+//! the injector's [`Clock`].
+
+use super::clock::{Clock, Duration, Instant, SystemClock};
+
+/// Configuration for a [`FaultInjector`]
+#[derive(Debug, Clone)]
+pub struct FaultInjectorConfig {
+    /// Probability (per-mille, 0-1000) that a packet is dropped outright
+    pub drop_chance_per_mille: u16,
+    /// Probability (per-mille, 0-1000) that a surviving packet's last byte is corrupted
+    pub corrupt_chance_per_mille: u16,
+    /// Probability (per-mille, 0-1000) that a surviving packet is held back one slot and
+    /// swapped with whatever packet is currently held, delivering packets out of order
+    pub reorder_chance_per_mille: u16,
+    /// Truncate packets larger than this many bytes, if set
+    pub max_packet_size: Option<usize>,
+    /// Token-bucket cap on egress bytes/sec, if set
+    pub max_tx_rate: Option<u64>,
+    /// Token-bucket cap on ingress bytes/sec, if set
+    pub max_rx_rate: Option<u64>,
+    /// Window over which `max_tx_rate`/`max_rx_rate` are enforced
+    pub shaping_interval: Duration,
+    /// Seed combined with each packet's sequence number to derive that packet's fault rolls
+    pub seed: u64,
+}
+
+impl Default for FaultInjectorConfig {
+    fn default() -> Self {
+        Self {
+            drop_chance_per_mille: 0,
+            corrupt_chance_per_mille: 0,
+            reorder_chance_per_mille: 0,
+            max_packet_size: None,
+            max_tx_rate: None,
+            max_rx_rate: None,
+            shaping_interval: Duration::from_secs(1),
+            seed: 0,
+        }
+    }
+}
+
+/// Result of running a packet through a [`FaultInjector`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultOutcome {
+    /// The packet passed through, possibly truncated or corrupted in place
+    Passed,
+    /// The packet was dropped by `drop_chance_per_mille`
+    Dropped,
+    /// The packet was dropped because the token bucket for its direction was exhausted
+    RateLimited,
+    /// The packet was held back and an earlier, previously-held packet was delivered in its
+    /// place; `data` now holds that earlier packet's bytes instead of the one passed in
+    Reordered,
+    /// The packet was held back for future out-of-order delivery; nothing is delivered this call
+    Held,
+}
+
+/// Fixed-size token bucket enforcing a bytes/sec cap over `interval`-sized windows
+#[derive(Debug)]
+struct TokenBucket {
+    limit_bytes_per_interval: u64,
+    interval: Duration,
+    window_start: Instant,
+    used_bytes: u64,
+}
+
+impl TokenBucket {
+    fn new(rate_bytes_per_sec: u64, interval: Duration, now: Instant) -> Self {
+        Self {
+            limit_bytes_per_interval: (rate_bytes_per_sec as f64 * interval.as_secs_f64()) as u64,
+            interval,
+            window_start: now,
+            used_bytes: 0,
+        }
+    }
+
+    fn allow(&mut self, size: usize, now: Instant) -> bool {
+        if now.duration_since(self.window_start) >= self.interval {
+            self.window_start = now;
+            self.used_bytes = 0;
+        }
+        if self.used_bytes + size as u64 > self.limit_bytes_per_interval {
+            false
+        } else {
+            self.used_bytes += size as u64;
+            true
+        }
+    }
+}
+
+/// Probabilistically degrades traffic on the adapter's datapath so generated protocols can be
+/// evaluated under adverse conditions instead of idealized ones
+pub struct FaultInjector {
+    config: FaultInjectorConfig,
+    packet_counter: u64,
+    /// Source of [`Instant`]s driving the token buckets' windows; [`SystemClock`] by default,
+    /// swappable for a [`super::ManualClock`] in tests
+    clock: Box<dyn Clock>,
+    tx_bucket: Option<TokenBucket>,
+    rx_bucket: Option<TokenBucket>,
+    /// Packet held back by a previous reorder roll, per direction, awaiting release
+    tx_reorder_slot: Option<Vec<u8>>,
+    rx_reorder_slot: Option<Vec<u8>>,
+}
+
+impl FaultInjector {
+    /// Create a new injector from `config`, rate-limited against the system clock
+    pub fn new(config: FaultInjectorConfig) -> Self {
+        Self::with_clock(config, Box::new(SystemClock::new()))
+    }
+
+    /// Create a new injector from `config`, rate-limited against `clock` instead of the system
+    /// clock, so token-bucket decisions can be tested deterministically
+    pub fn with_clock(config: FaultInjectorConfig, clock: Box<dyn Clock>) -> Self {
+        let now = clock.now();
+        let tx_bucket = config.max_tx_rate.map(|rate| TokenBucket::new(rate, config.shaping_interval, now));
+        let rx_bucket = config.max_rx_rate.map(|rate| TokenBucket::new(rate, config.shaping_interval, now));
+        Self {
+            config,
+            packet_counter: 0,
+            clock,
+            tx_bucket,
+            rx_bucket,
+            tx_reorder_slot: None,
+            rx_reorder_slot: None,
+        }
+    }
+
+    /// Apply configured faults to an outgoing packet
+    pub fn inject_tx(&mut self, data: &mut Vec<u8>) -> FaultOutcome {
+        self.inject(data, true)
+    }
+
+    /// Apply configured faults to an incoming packet
+    pub fn inject_rx(&mut self, data: &mut Vec<u8>) -> FaultOutcome {
+        self.inject(data, false)
+    }
+
+    fn inject(&mut self, data: &mut Vec<u8>, tx: bool) -> FaultOutcome {
+        let packet_no = self.packet_counter;
+        self.packet_counter += 1;
+
+        if self.roll(packet_no, 0) < self.config.drop_chance_per_mille as f64 / 1000.0 {
+            return FaultOutcome::Dropped;
+        }
+
+        let now = self.clock.now();
+        let bucket = if tx { self.tx_bucket.as_mut() } else { self.rx_bucket.as_mut() };
+        if let Some(bucket) = bucket {
+            if !bucket.allow(data.len(), now) {
+                return FaultOutcome::RateLimited;
+            }
+        }
+
+        if let Some(max_size) = self.config.max_packet_size {
+            data.truncate(max_size);
+        }
+
+        if self.roll(packet_no, 1) < self.config.corrupt_chance_per_mille as f64 / 1000.0 {
+            if let Some(byte) = data.last_mut() {
+                *byte ^= (self.roll_u64(packet_no, 2) % 255) as u8 + 1;
+            }
+        }
+
+        if self.roll(packet_no, 3) < self.config.reorder_chance_per_mille as f64 / 1000.0 {
+            let slot = if tx { &mut self.tx_reorder_slot } else { &mut self.rx_reorder_slot };
+            return match slot.replace(data.clone()) {
+                Some(held) => {
+                    *data = held;
+                    FaultOutcome::Reordered
+                }
+                None => FaultOutcome::Held,
+            };
+        }
+
+        FaultOutcome::Passed
+    }
+
+    /// A reproducible `[0, 1)` draw for packet `packet_no`, salted so drop/corrupt rolls for
+    /// the same packet are independent
+    fn roll(&self, packet_no: u64, salt: u64) -> f64 {
+        (self.roll_u64(packet_no, salt) >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// xorshift64, seeded from the injector's seed, the packet number, and a salt
+    fn roll_u64(&self, packet_no: u64, salt: u64) -> u64 {
+        let mut state = self.config.seed
+            ^ packet_no.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ salt.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_replays_identically() {
+        let config = FaultInjectorConfig { drop_chance_per_mille: 200, seed: 7, ..Default::default() };
+        let mut a = FaultInjector::new(config.clone());
+        let mut b = FaultInjector::new(config);
+
+        let outcomes_a: Vec<_> = (0..50).map(|_| a.inject_tx(&mut vec![1, 2, 3])).collect();
+        let outcomes_b: Vec<_> = (0..50).map(|_| b.inject_tx(&mut vec![1, 2, 3])).collect();
+
+        assert_eq!(outcomes_a, outcomes_b);
+    }
+
+    #[test]
+    fn reorder_holds_then_swaps() {
+        let config = FaultInjectorConfig { reorder_chance_per_mille: 1000, ..Default::default() };
+        let mut injector = FaultInjector::new(config);
+
+        let mut first = vec![1u8];
+        assert_eq!(injector.inject_tx(&mut first), FaultOutcome::Held);
+
+        let mut second = vec![2u8];
+        assert_eq!(injector.inject_tx(&mut second), FaultOutcome::Reordered);
+        assert_eq!(second, vec![1u8]);
+    }
+
+    #[test]
+    fn max_packet_size_truncates() {
+        let config = FaultInjectorConfig { max_packet_size: Some(4), ..Default::default() };
+        let mut injector = FaultInjector::new(config);
+        let mut data = vec![0u8; 16];
+
+        assert_eq!(injector.inject_tx(&mut data), FaultOutcome::Passed);
+        assert_eq!(data.len(), 4);
+    }
+}
@@ -0,0 +1,239 @@
+//! Hook-based packet filtering for deployed protocols, modeled on nftables-style base chains:
+//! ordered [`Rule`]s are grouped into [`Routine`]s, routines are grouped into named [`Hook`]s,
+//! and a walk through a hook yields an Accept/Drop [`Verdict`] instead of the log lines
+//! `apply_security_parameters` used to produce.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::NetworkInterface;
+
+/// Final disposition of a packet after walking a [`Hook`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Let the packet through
+    Accept,
+    /// Discard the packet
+    Drop,
+}
+
+/// Action a matching [`Rule`] applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Accept the packet, short-circuiting the whole hook
+    Accept,
+    /// Drop the packet, short-circuiting the whole hook
+    Drop,
+    /// Stop evaluating the current routine and fall through to the hook's next routine
+    Return,
+}
+
+/// The point in a deployed protocol's datapath a [`Hook`] is evaluated at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookPoint {
+    /// Traffic arriving on an interface, before local delivery
+    Ingress,
+    /// Traffic leaving on an interface
+    Egress,
+    /// Traffic destined for this host
+    LocalIngress,
+    /// Traffic originated by this host
+    LocalEgress,
+}
+
+/// Minimal packet metadata a [`Rule`] matcher can test, alongside the ingress/egress
+/// [`NetworkInterface`] it was observed on
+#[derive(Debug, Clone)]
+pub struct PacketMeta {
+    /// Source address
+    pub src: String,
+    /// Destination address
+    pub dst: String,
+    /// Packet size in bytes
+    pub size: usize,
+}
+
+/// A single filter rule: a matcher over packet and interface properties, and the action to
+/// take when it matches, with a running hit count for [`RealProtocolAdapter::collect_stats`]
+pub struct Rule {
+    name: String,
+    matcher: Box<dyn Fn(&PacketMeta, &NetworkInterface) -> bool + Send + Sync>,
+    action: Action,
+    hits: AtomicU64,
+}
+
+impl std::fmt::Debug for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rule")
+            .field("name", &self.name)
+            .field("action", &self.action)
+            .field("hits", &self.hit_count())
+            .finish()
+    }
+}
+
+impl Rule {
+    /// Create a new rule with the given name, action, and matcher predicate
+    pub fn new(
+        name: &str,
+        action: Action,
+        matcher: impl Fn(&PacketMeta, &NetworkInterface) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            matcher: Box::new(matcher),
+            action,
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Rule name, reported alongside its hit count
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of times this rule has matched a packet
+    pub fn hit_count(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    fn evaluate(&self, pkt: &PacketMeta, iface: &NetworkInterface) -> Option<Action> {
+        if (self.matcher)(pkt, iface) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            Some(self.action)
+        } else {
+            None
+        }
+    }
+}
+
+/// An ordered group of [`Rule`]s evaluated top to bottom within a [`Hook`]
+#[derive(Debug, Default)]
+pub struct Routine {
+    /// Routine name, used only for diagnostics
+    pub name: String,
+    rules: Vec<Rule>,
+}
+
+impl Routine {
+    /// Create a new, empty routine
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), rules: Vec::new() }
+    }
+
+    /// Append a rule to the end of the routine
+    pub fn add_rule(&mut self, rule: Rule) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// All rules in this routine, in evaluation order
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Walk rules in order; the first matcher that matches yields its action. Returns
+    /// `Some(verdict)` when an `Accept`/`Drop` rule matched (the hook should stop), or `None`
+    /// when no rule matched or a `Return` rule matched (the hook should try its next routine).
+    fn evaluate(&self, pkt: &PacketMeta, iface: &NetworkInterface) -> Option<Verdict> {
+        for rule in &self.rules {
+            match rule.evaluate(pkt, iface) {
+                Some(Action::Accept) => return Some(Verdict::Accept),
+                Some(Action::Drop) => return Some(Verdict::Drop),
+                Some(Action::Return) => return None,
+                None => continue,
+            }
+        }
+        None
+    }
+}
+
+/// A named hook point made up of ordered [`Routine`]s, plus the verdict applied when no rule
+/// in any routine matches (nftables base-chain policy)
+#[derive(Debug)]
+pub struct Hook {
+    /// Hook point this applies to
+    pub point: HookPoint,
+    /// Verdict applied when every routine falls through without an Accept/Drop match
+    pub default_verdict: Verdict,
+    routines: Vec<Routine>,
+}
+
+impl Hook {
+    /// Create a new hook with no routines and the given default (base-chain policy) verdict
+    pub fn new(point: HookPoint, default_verdict: Verdict) -> Self {
+        Self { point, default_verdict, routines: Vec::new() }
+    }
+
+    /// Append a routine to the end of the hook
+    pub fn add_routine(&mut self, routine: Routine) -> &mut Self {
+        self.routines.push(routine);
+        self
+    }
+
+    /// All routines in this hook, in evaluation order
+    pub fn routines(&self) -> &[Routine] {
+        &self.routines
+    }
+
+    /// Evaluate this hook against a packet observed on `iface`, falling back to
+    /// [`Self::default_verdict`] when nothing matches
+    pub fn evaluate(&self, pkt: &PacketMeta, iface: &NetworkInterface) -> Verdict {
+        for routine in &self.routines {
+            if let Some(verdict) = routine.evaluate(pkt, iface) {
+                return verdict;
+            }
+        }
+        self.default_verdict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol_adapter::NetworkInterfaceType;
+
+    fn eth0() -> NetworkInterface {
+        NetworkInterface {
+            name: "eth0".to_string(),
+            interface_type: NetworkInterfaceType::Ethernet,
+            active: true,
+            stats: Default::default(),
+        }
+    }
+
+    fn pkt() -> PacketMeta {
+        PacketMeta { src: "10.0.0.1".to_string(), dst: "10.0.0.2".to_string(), size: 64 }
+    }
+
+    #[test]
+    fn falls_through_to_default_verdict() {
+        let hook = Hook::new(HookPoint::Ingress, Verdict::Drop);
+        assert_eq!(hook.evaluate(&pkt(), &eth0()), Verdict::Drop);
+    }
+
+    #[test]
+    fn return_continues_to_next_routine() {
+        let mut first = Routine::new("first");
+        first.add_rule(Rule::new("return-all", Action::Return, |_, _| true));
+        let mut second = Routine::new("second");
+        second.add_rule(Rule::new("accept-all", Action::Accept, |_, _| true));
+
+        let mut hook = Hook::new(HookPoint::Egress, Verdict::Drop);
+        hook.add_routine(first).add_routine(second);
+
+        assert_eq!(hook.evaluate(&pkt(), &eth0()), Verdict::Accept);
+    }
+
+    #[test]
+    fn matching_rule_increments_hit_count() {
+        let mut routine = Routine::new("count");
+        routine.add_rule(Rule::new("drop-all", Action::Drop, |_, _| true));
+        let mut hook = Hook::new(HookPoint::Ingress, Verdict::Accept);
+        hook.add_routine(routine);
+
+        hook.evaluate(&pkt(), &eth0());
+        hook.evaluate(&pkt(), &eth0());
+
+        assert_eq!(hook.routines()[0].rules()[0].hit_count(), 2);
+    }
+}
@@ -1,9 +1,290 @@
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::cell::RefCell;
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
 
 // Import from the original codebase
 use unrealnet_core::dynphys::adaptor::{NetworkCondition, AdaptorState};
 
+/// Result of the GCC overuse detector for one packet group
+pub use crate::gcc::OveruseSignal;
+
+/// Google-Congestion-Control (GCC) style delay-based bandwidth estimator.
+///
+/// Consumes send/arrival timestamps for packet groups, derives a one-way-delay
+/// gradient trendline, runs an adaptive overuse detector against it, and drives
+/// a three-state (increase/hold/decrease) rate controller to produce a running
+/// `estimated_bitrate` in Kbps.
+///
+/// The trendline regression and adaptive threshold are [`crate::gcc::DelayGradientTrendline`],
+/// shared with `simulation::bandwidth_estimator::DelayGradientEstimator` rather than
+/// reimplemented here. The overuse persistence gate below it is this estimator's own, and
+/// deliberately stays a single-timestamp `overuse_since_ms` check rather than
+/// [`crate::gcc::GccDelayEstimator`]'s multi-sample count check: that estimator buckets raw
+/// per-packet arrivals into groups itself and sees many samples per unit time, where a count
+/// gate makes sense, while this one receives one already-grouped sample per call from its caller,
+/// where a single-timestamp hold is the natural equivalent.
+#[derive(Debug, Clone)]
+pub struct BandwidthEstimator {
+    /// Shared delay-gradient trendline and adaptive threshold
+    trendline: crate::gcc::DelayGradientTrendline,
+    /// Send time of the previous packet group, in ms
+    last_send_time_ms: Option<f64>,
+    /// Arrival time of the previous packet group, in ms
+    last_arrival_time_ms: Option<f64>,
+    /// Arrival time (ms) at which the trend first crossed the threshold, if still rising
+    overuse_since_ms: Option<f64>,
+    /// Current delay-based bitrate estimate, in Kbps
+    estimate_kbps: f64,
+    /// Estimate just before the most recent decrease (the initial estimate until the first
+    /// one), used to pick increase mode
+    last_max_kbps: f64,
+}
+
+/// Minimum duration the trend must stay above threshold before declaring overuse, in ms
+const OVERUSE_HOLD_MS: f64 = 10.0;
+
+impl BandwidthEstimator {
+    /// Create a new estimator seeded with an initial bitrate estimate, in Kbps
+    pub fn new(initial_estimate_kbps: f64) -> Self {
+        Self {
+            trendline: crate::gcc::DelayGradientTrendline::new(),
+            last_send_time_ms: None,
+            last_arrival_time_ms: None,
+            overuse_since_ms: None,
+            estimate_kbps: initial_estimate_kbps,
+            last_max_kbps: initial_estimate_kbps,
+        }
+    }
+
+    /// Feed the send/arrival timestamps (ms) of the next packet group through the
+    /// delay-gradient trendline, overuse detector and rate controller, updating
+    /// `estimate_kbps` and returning the detected signal.
+    pub fn on_packet_group(&mut self, send_time_ms: f64, arrival_time_ms: f64) -> OveruseSignal {
+        let (last_send, last_arrival) = match (self.last_send_time_ms, self.last_arrival_time_ms) {
+            (Some(s), Some(a)) => (s, a),
+            _ => {
+                self.last_send_time_ms = Some(send_time_ms);
+                self.last_arrival_time_ms = Some(arrival_time_ms);
+                self.trendline.seed_timestamp(arrival_time_ms);
+                return OveruseSignal::Normal;
+            }
+        };
+
+        // One-way-delay gradient: d(i) = (arrival(i) - arrival(i-1)) - (send(i) - send(i-1))
+        let gradient = (arrival_time_ms - last_arrival) - (send_time_ms - last_send);
+        self.last_send_time_ms = Some(send_time_ms);
+        self.last_arrival_time_ms = Some(arrival_time_ms);
+
+        let trend = self.trendline.update(arrival_time_ms, gradient);
+        let threshold = self.trendline.threshold_ms();
+
+        let signal = if trend > threshold {
+            let since = *self.overuse_since_ms.get_or_insert(arrival_time_ms);
+            if arrival_time_ms - since > OVERUSE_HOLD_MS {
+                OveruseSignal::Overuse
+            } else {
+                OveruseSignal::Normal
+            }
+        } else if trend < -threshold {
+            self.overuse_since_ms = None;
+            OveruseSignal::Underuse
+        } else {
+            self.overuse_since_ms = None;
+            OveruseSignal::Normal
+        };
+
+        self.apply_rate_control(signal);
+        signal
+    }
+
+    /// Three-state rate controller: Overuse decreases, Normal increases
+    /// (multiplicatively while far from the last known ceiling, additively near it),
+    /// Underuse holds the current estimate.
+    fn apply_rate_control(&mut self, signal: OveruseSignal) {
+        match signal {
+            OveruseSignal::Overuse => {
+                self.last_max_kbps = self.estimate_kbps;
+                self.estimate_kbps *= 0.85;
+            }
+            OveruseSignal::Normal => {
+                if self.estimate_kbps < self.last_max_kbps * 0.9 {
+                    self.estimate_kbps *= 1.08;
+                } else {
+                    self.estimate_kbps += 1.0;
+                }
+            }
+            OveruseSignal::Underuse => {
+                // Hold: no change to the estimate
+            }
+        }
+        self.estimate_kbps = self.estimate_kbps.max(1.0);
+    }
+
+    /// Combine the delay-based estimate with a loss-based estimate derived from
+    /// `loss_fraction` (0.0-1.0), taking the minimum of the two, and return the
+    /// resulting bitrate estimate in Kbps.
+    pub fn combine_with_loss(&mut self, loss_fraction: f64) -> f64 {
+        let loss_estimate = if loss_fraction > 0.1 {
+            self.estimate_kbps * (1.0 - 0.5 * loss_fraction)
+        } else if loss_fraction < 0.02 {
+            self.estimate_kbps * 1.05
+        } else {
+            self.estimate_kbps
+        };
+
+        self.estimate_kbps = self.estimate_kbps.min(loss_estimate).max(1.0);
+        self.estimate_kbps
+    }
+
+    /// Current bitrate estimate, in Kbps
+    pub fn estimated_bitrate(&self) -> f64 {
+        self.estimate_kbps
+    }
+}
+
+/// Number of connect-timing probes sent per measurement
+const PROBE_COUNT: usize = 5;
+/// Per-probe connect timeout
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+/// Size of the payload written to estimate bandwidth from transfer timing
+const BANDWIDTH_PROBE_BYTES: usize = 16 * 1024;
+
+/// Source of raw latency/bandwidth/loss/jitter samples for a `RealNetworkAdaptor`.
+/// Production code measures real sockets; tests substitute a deterministic
+/// simulated backend instead.
+pub trait MeasurementBackend: std::fmt::Debug {
+    /// Measure network latency to an endpoint in milliseconds
+    fn measure_latency(&self, endpoint: &str) -> Option<f64>;
+    /// Estimate bandwidth to an endpoint in Kbps
+    fn measure_bandwidth(&self, endpoint: &str) -> Option<f64>;
+    /// Estimate packet loss as a percentage (0-100)
+    fn measure_packet_loss(&self, endpoint: &str) -> Option<f64>;
+    /// Measure jitter (variation in latency) in milliseconds
+    fn measure_jitter(&self, endpoint: &str) -> Option<f64>;
+}
+
+/// Measures real network conditions over TCP: latency and packet loss from
+/// repeated TCP-connect probes, jitter from the RFC 3550 interarrival-jitter
+/// EWMA over successive RTT samples, and bandwidth from timing a fixed-size
+/// probe transfer.
+#[derive(Debug, Default)]
+pub struct TcpMeasurementBackend {
+    /// Per-endpoint RFC 3550 jitter state: (last transit time ms, current jitter ms)
+    jitter_state: RefCell<HashMap<String, (f64, f64)>>,
+}
+
+impl TcpMeasurementBackend {
+    /// Create a new TCP-probing measurement backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `endpoint` (hostname:port) to a socket address
+    fn resolve(endpoint: &str) -> Option<SocketAddr> {
+        endpoint.to_socket_addrs().ok()?.next()
+    }
+
+    /// Connect-RTT (ms) for each successful probe out of `PROBE_COUNT` attempts
+    fn probe_rtts(addr: SocketAddr) -> Vec<f64> {
+        let mut rtts = Vec::with_capacity(PROBE_COUNT);
+        for _ in 0..PROBE_COUNT {
+            let start = Instant::now();
+            if TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).is_ok() {
+                rtts.push(start.elapsed().as_secs_f64() * 1000.0);
+            }
+        }
+        rtts
+    }
+}
+
+impl MeasurementBackend for TcpMeasurementBackend {
+    fn measure_latency(&self, endpoint: &str) -> Option<f64> {
+        let addr = Self::resolve(endpoint)?;
+        let mut rtts = Self::probe_rtts(addr);
+        if rtts.is_empty() {
+            return None;
+        }
+
+        // p50 of the successful connect RTTs
+        rtts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Some(rtts[rtts.len() / 2])
+    }
+
+    fn measure_bandwidth(&self, endpoint: &str) -> Option<f64> {
+        let addr = Self::resolve(endpoint)?;
+        let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).ok()?;
+        let payload = vec![0u8; BANDWIDTH_PROBE_BYTES];
+
+        let start = Instant::now();
+        stream.write_all(&payload).ok()?;
+        stream.flush().ok()?;
+        let elapsed_secs = start.elapsed().as_secs_f64().max(0.0001);
+
+        // Kbps = bits transferred / 1000 / seconds elapsed
+        Some((BANDWIDTH_PROBE_BYTES as f64 * 8.0 / 1000.0) / elapsed_secs)
+    }
+
+    fn measure_packet_loss(&self, endpoint: &str) -> Option<f64> {
+        let addr = Self::resolve(endpoint)?;
+
+        let mut failures = 0;
+        for _ in 0..PROBE_COUNT {
+            if TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).is_err() {
+                failures += 1;
+            }
+        }
+        Some(failures as f64 / PROBE_COUNT as f64 * 100.0)
+    }
+
+    fn measure_jitter(&self, endpoint: &str) -> Option<f64> {
+        let addr = Self::resolve(endpoint)?;
+        let transit = *Self::probe_rtts(addr).last()?;
+
+        let mut state = self.jitter_state.borrow_mut();
+        let (last_transit, last_jitter) = *state.get(endpoint).unwrap_or(&(transit, 0.0));
+
+        // RFC 3550 interarrival jitter: J = J + (|D| - J) / 16
+        let d = transit - last_transit;
+        let jitter = last_jitter + (d.abs() - last_jitter) / 16.0;
+
+        state.insert(endpoint.to_string(), (transit, jitter));
+        Some(jitter)
+    }
+}
+
+/// Fabricated, wall-clock-derived measurements kept for tests and environments
+/// without real network access, so `RealNetworkAdaptor` stays exercisable offline.
+#[derive(Debug, Default)]
+pub struct SimulatedMeasurementBackend;
+
+impl MeasurementBackend for SimulatedMeasurementBackend {
+    fn measure_latency(&self, _endpoint: &str) -> Option<f64> {
+        Some(50.0 + (SystemTime::now().duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_millis() % 50) as f64)
+    }
+
+    fn measure_bandwidth(&self, _endpoint: &str) -> Option<f64> {
+        Some(5000.0 + (SystemTime::now().duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_millis() % 5000) as f64)
+    }
+
+    fn measure_packet_loss(&self, _endpoint: &str) -> Option<f64> {
+        Some((SystemTime::now().duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_millis() % 4) as f64)
+    }
+
+    fn measure_jitter(&self, _endpoint: &str) -> Option<f64> {
+        Some(5.0 + (SystemTime::now().duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_millis() % 20) as f64 / 10.0)
+    }
+}
+
 /// Real network adaptor that measures actual network conditions
 pub struct RealNetworkAdaptor {
     /// Unique identifier
@@ -22,11 +303,21 @@ pub struct RealNetworkAdaptor {
     measurement_interval: u64,
     /// Last measurement time
     last_measurement: Option<Instant>,
+    /// GCC-style delay-based bandwidth estimator fed by each sensing pass
+    bandwidth_estimator: BandwidthEstimator,
+    /// Source of raw latency/bandwidth/loss/jitter samples
+    backend: Box<dyn MeasurementBackend>,
 }
 
 impl RealNetworkAdaptor {
-    /// Create a new real network adaptor
+    /// Create a new real network adaptor, measuring endpoints over real TCP sockets
     pub fn new(id: &str, name: &str) -> Self {
+        Self::with_backend(id, name, Box::new(TcpMeasurementBackend::new()))
+    }
+
+    /// Create a new real network adaptor with a specific measurement backend,
+    /// e.g. a `SimulatedMeasurementBackend` for tests without real network access
+    pub fn with_backend(id: &str, name: &str, backend: Box<dyn MeasurementBackend>) -> Self {
         Self {
             id: id.to_string(),
             name: name.to_string(),
@@ -36,9 +327,11 @@ impl RealNetworkAdaptor {
             max_history: 100,
             measurement_interval: 1000, // Default: measure every second
             last_measurement: None,
+            bandwidth_estimator: BandwidthEstimator::new(1000.0),
+            backend,
         }
     }
-    
+
     /// Add a target endpoint to measure against (hostname:port)
     pub fn add_endpoint(&mut self, endpoint: &str) -> &mut Self {
         self.endpoints.push(endpoint.to_string());
@@ -66,7 +359,13 @@ impl RealNetworkAdaptor {
     pub fn pause(&mut self) {
         self.state = AdaptorState::Paused;
     }
-    
+
+    /// Current GCC-estimated bitrate, in Kbps, so downstream `AdaptorState` logic
+    /// can react to it without recomputing the estimator itself
+    pub fn estimated_bitrate(&self) -> f64 {
+        self.bandwidth_estimator.estimated_bitrate()
+    }
+
     /// Measure real network conditions
     pub fn sense_environment(&mut self) -> Option<Vec<NetworkCondition>> {
         if self.state != AdaptorState::Active {
@@ -90,11 +389,16 @@ impl RealNetworkAdaptor {
             .as_secs();
             
         let mut conditions = Vec::new();
-        
+        let send_time_ms = now as f64 * 1000.0;
+
         // Measure latency for each endpoint
         for endpoint in &self.endpoints {
+            let mut endpoint_latency = None;
+            let mut endpoint_packet_loss = None;
+
             // Measure latency
             if let Some(latency) = self.measure_latency(endpoint) {
+                endpoint_latency = Some(latency);
                 let latency_condition = NetworkCondition {
                     name: "latency".to_string(),
                     value: normalize_latency(latency),
@@ -103,7 +407,7 @@ impl RealNetworkAdaptor {
                 self.record_measurement(latency_condition.clone());
                 conditions.push(latency_condition);
             }
-            
+
             // Measure bandwidth
             if let Some(bandwidth) = self.measure_bandwidth(endpoint) {
                 let bandwidth_condition = NetworkCondition {
@@ -114,9 +418,10 @@ impl RealNetworkAdaptor {
                 self.record_measurement(bandwidth_condition.clone());
                 conditions.push(bandwidth_condition);
             }
-            
+
             // Measure packet loss
             if let Some(packet_loss) = self.measure_packet_loss(endpoint) {
+                endpoint_packet_loss = Some(packet_loss);
                 let packet_loss_condition = NetworkCondition {
                     name: "packet_loss".to_string(),
                     value: normalize_packet_loss(packet_loss),
@@ -125,7 +430,7 @@ impl RealNetworkAdaptor {
                 self.record_measurement(packet_loss_condition.clone());
                 conditions.push(packet_loss_condition);
             }
-            
+
             // Measure jitter
             if let Some(jitter) = self.measure_jitter(endpoint) {
                 let jitter_condition = NetworkCondition {
@@ -136,8 +441,26 @@ impl RealNetworkAdaptor {
                 self.record_measurement(jitter_condition.clone());
                 conditions.push(jitter_condition);
             }
+
+            // Feed the delay-gradient trendline with this endpoint's one-way delay
+            // (send_time → send_time + latency), then fold in loss-based estimation
+            if let Some(latency) = endpoint_latency {
+                let arrival_time_ms = send_time_ms + latency;
+                self.bandwidth_estimator.on_packet_group(send_time_ms, arrival_time_ms);
+
+                let loss_fraction = endpoint_packet_loss.unwrap_or(0.0) / 100.0;
+                let estimated_bitrate = self.bandwidth_estimator.combine_with_loss(loss_fraction);
+
+                let bitrate_condition = NetworkCondition {
+                    name: "estimated_bitrate".to_string(),
+                    value: normalize_bandwidth(estimated_bitrate),
+                    timestamp: now,
+                };
+                self.record_measurement(bitrate_condition.clone());
+                conditions.push(bitrate_condition);
+            }
         }
-        
+
         if conditions.is_empty() {
             None
         } else {
@@ -157,35 +480,23 @@ impl RealNetworkAdaptor {
     }
 
     /// Measure network latency to an endpoint in milliseconds
-    pub fn measure_latency(&self, _endpoint: &str) -> Option<f64> {
-        // For demonstration purposes, return a simulated value
-        Some(50.0 + (SystemTime::now().duration_since(UNIX_EPOCH)
-            .unwrap_or_else(|_| Duration::from_secs(0))
-            .as_millis() % 50) as f64)
+    pub fn measure_latency(&self, endpoint: &str) -> Option<f64> {
+        self.backend.measure_latency(endpoint)
     }
-    
+
     /// Estimate bandwidth to an endpoint in Kbps
-    pub fn measure_bandwidth(&self, _endpoint: &str) -> Option<f64> {
-        // For demonstration, provide a simulated value
-        Some(5000.0 + (SystemTime::now().duration_since(UNIX_EPOCH)
-            .unwrap_or_else(|_| Duration::from_secs(0))
-            .as_millis() % 5000) as f64)
+    pub fn measure_bandwidth(&self, endpoint: &str) -> Option<f64> {
+        self.backend.measure_bandwidth(endpoint)
     }
-    
+
     /// Estimate packet loss as a percentage (0-100)
-    pub fn measure_packet_loss(&self, _endpoint: &str) -> Option<f64> {
-        // For demonstration, simulate a small packet loss
-        Some((SystemTime::now().duration_since(UNIX_EPOCH)
-            .unwrap_or_else(|_| Duration::from_secs(0))
-            .as_millis() % 4) as f64)
+    pub fn measure_packet_loss(&self, endpoint: &str) -> Option<f64> {
+        self.backend.measure_packet_loss(endpoint)
     }
-    
+
     /// Measure jitter (variation in latency) in milliseconds
-    pub fn measure_jitter(&self, _endpoint: &str) -> Option<f64> {
-        // For demonstration, simulate a jitter value
-        Some(5.0 + (SystemTime::now().duration_since(UNIX_EPOCH)
-            .unwrap_or_else(|_| Duration::from_secs(0))
-            .as_millis() % 20) as f64 / 10.0)
+    pub fn measure_jitter(&self, endpoint: &str) -> Option<f64> {
+        self.backend.measure_jitter(endpoint)
     }
 }
 
@@ -249,4 +560,52 @@ mod tests {
         assert_eq!(normalize_jitter(0.0), 1.0); // No jitter
         assert_eq!(normalize_jitter(100.0), 0.0); // High jitter
     }
+
+    #[test]
+    fn bandwidth_estimator_decreases_on_growing_delay() {
+        let mut estimator = BandwidthEstimator::new(1000.0);
+
+        // Steadily growing one-way delay should eventually trip the overuse detector
+        let mut send = 0.0;
+        let mut arrival = 0.0;
+        let mut saw_overuse = false;
+        for i in 0..40 {
+            send += 20.0;
+            arrival += 20.0 + i as f64; // arrival falls further behind send each group
+            if estimator.on_packet_group(send, arrival) == OveruseSignal::Overuse {
+                saw_overuse = true;
+            }
+        }
+
+        assert!(saw_overuse, "growing delay gradient should trigger an overuse signal");
+        assert!(estimator.estimated_bitrate() < 1000.0);
+    }
+
+    #[test]
+    fn bandwidth_estimator_combines_with_loss() {
+        let mut estimator = BandwidthEstimator::new(1000.0);
+        let high_loss_estimate = estimator.combine_with_loss(0.2);
+        assert!(high_loss_estimate < 1000.0);
+
+        let mut estimator = BandwidthEstimator::new(1000.0);
+        let low_loss_estimate = estimator.combine_with_loss(0.01);
+        assert_eq!(low_loss_estimate, 1000.0); // delay-based estimate is the binding minimum
+    }
+
+    #[test]
+    fn simulated_backend_never_fails() {
+        let backend = SimulatedMeasurementBackend;
+        assert!(backend.measure_latency("unreachable:1").is_some());
+        assert!(backend.measure_bandwidth("unreachable:1").is_some());
+        assert!(backend.measure_packet_loss("unreachable:1").is_some());
+        assert!(backend.measure_jitter("unreachable:1").is_some());
+    }
+
+    #[test]
+    fn tcp_backend_reports_none_for_unresolvable_endpoint() {
+        let backend = TcpMeasurementBackend::new();
+        assert_eq!(backend.measure_latency("not-a-real-host.invalid:1"), None);
+        assert_eq!(backend.measure_bandwidth("not-a-real-host.invalid:1"), None);
+        assert_eq!(backend.measure_jitter("not-a-real-host.invalid:1"), None);
+    }
 }
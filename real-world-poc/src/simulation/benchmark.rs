@@ -0,0 +1,194 @@
+//! Per-round benchmark measurements: wall-clock compute time, bytes sent, message count, and
+//! per-node protocol switch counts for each adaptation round, aggregated into
+//! [`BenchmarkResults`] (round index -> metrics) so a report can show how quickly an adaptation
+//! strategy converges under each scenario, not just its final improvement.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::metrics::ErrorString;
+use super::network::NetworkSimulation;
+
+/// Messages assumed per connection per simulated second, for [`Measurements::record_round`]'s
+/// message-count estimate. Mirrors the simulation's ~100ms update cadence (10 ticks/sec) without
+/// coupling this module to `network::TICK_MS`.
+const MESSAGES_PER_CONNECTION_PER_SEC: u64 = 10;
+
+/// One adaptation round's measurements: a single scenario pass within `run_with_adaptation`
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundMeasurement {
+    /// Monotonically increasing round index across every repetition/scenario pass
+    pub round: usize,
+    /// Scenario name this round ran
+    pub scenario: String,
+    /// Wall-clock time this round's `runner.advance` call took to compute
+    pub wall_clock_ms: f64,
+    /// Bytes sent this round, derived from each connection's configured bitrate over the round's
+    /// simulated duration
+    pub bytes_sent: u64,
+    /// Estimated message count this round, derived from connection count and simulated duration
+    pub message_count: u64,
+    /// Number of protocol switches observed this round, keyed by node id
+    pub protocol_switches_by_node: HashMap<usize, usize>,
+}
+
+/// Round-indexed benchmark history for a run, collected by [`Measurements`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BenchmarkResults {
+    pub rounds: Vec<RoundMeasurement>,
+}
+
+impl BenchmarkResults {
+    /// Total protocol switches across every round and node
+    pub fn total_protocol_switches(&self) -> usize {
+        self.rounds.iter().flat_map(|round| round.protocol_switches_by_node.values()).sum()
+    }
+
+    /// Serialize every round as a single pretty-printed JSON array
+    pub fn write_json(&self, writer: &mut dyn Write) -> Result<(), ErrorString> {
+        let json = serde_json::to_string_pretty(&self.rounds)
+            .map_err(|e| ErrorString(format!("Failed to serialize benchmark results: {}", e)))?;
+        writeln!(writer, "{}", json)?;
+        Ok(())
+    }
+
+    /// One row per round, with per-node protocol switches summed into a single column
+    pub fn write_csv(&self, writer: &mut dyn Write) -> Result<(), ErrorString> {
+        writeln!(writer, "round,scenario,wall_clock_ms,bytes_sent,message_count,protocol_switches")?;
+        for round in &self.rounds {
+            let switches: usize = round.protocol_switches_by_node.values().sum();
+            writeln!(
+                writer,
+                "{},{},{:.2},{},{},{}",
+                round.round, round.scenario, round.wall_clock_ms, round.bytes_sent, round.message_count, switches,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write both the JSON and CSV forms to `<dir>/benchmark_results.{json,csv}`, creating `dir`
+    /// if needed
+    pub fn export_to_dir(&self, dir: &str) -> Result<(), ErrorString> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| ErrorString(format!("Failed to create output directory {}: {}", dir, e)))?;
+
+        let json_path = format!("{}/benchmark_results.json", dir);
+        let mut json_file = File::create(&json_path)
+            .map_err(|e| ErrorString(format!("Failed to create benchmark export file {}: {}", json_path, e)))?;
+        self.write_json(&mut json_file)?;
+
+        let csv_path = format!("{}/benchmark_results.csv", dir);
+        let mut csv_file = File::create(&csv_path)
+            .map_err(|e| ErrorString(format!("Failed to create benchmark export file {}: {}", csv_path, e)))?;
+        self.write_csv(&mut csv_file)
+    }
+}
+
+/// Records per-round benchmark measurements during `run_with_adaptation`: wall-clock compute
+/// time for the round's `runner.advance` call, bytes sent and message count derived from each
+/// connection's configured bitrate and the round's simulated duration, and protocol switches per
+/// node, diffed against the previous round's per-node protocol snapshot.
+pub struct Measurements {
+    last_protocol_by_node: HashMap<usize, String>,
+    results: BenchmarkResults,
+}
+
+impl Measurements {
+    pub fn new() -> Self {
+        Self { last_protocol_by_node: HashMap::new(), results: BenchmarkResults::default() }
+    }
+
+    /// Record one round. `round_duration` is the wall-clock time this round's `runner.advance` call
+    /// took; `simulated_secs` is the simulated time it covered.
+    pub fn record_round(
+        &mut self,
+        round: usize,
+        scenario: &str,
+        round_duration: Duration,
+        simulated_secs: u64,
+        network: &NetworkSimulation,
+    ) {
+        let mut bytes_sent = 0u64;
+        let mut message_count = 0u64;
+        let mut switches_by_node: HashMap<usize, usize> = HashMap::new();
+        let mut protocol_this_round: HashMap<usize, String> = HashMap::new();
+
+        for conn in network.get_connections() {
+            // bits/sec -> bytes over the simulated duration of this round
+            bytes_sent += ((conn.bandwidth.bps() as f64 / 8.0) * simulated_secs as f64) as u64;
+            message_count += simulated_secs * MESSAGES_PER_CONNECTION_PER_SEC;
+
+            if let Some(protocol) = &conn.active_protocol {
+                for node_id in [conn.source_id, conn.dest_id] {
+                    protocol_this_round.entry(node_id).or_insert_with(|| protocol.clone());
+                }
+            }
+        }
+
+        for (node_id, protocol) in &protocol_this_round {
+            if matches!(self.last_protocol_by_node.get(node_id), Some(previous) if previous != protocol) {
+                *switches_by_node.entry(*node_id).or_insert(0) += 1;
+            }
+        }
+        self.last_protocol_by_node.extend(protocol_this_round);
+
+        self.results.rounds.push(RoundMeasurement {
+            round,
+            scenario: scenario.to_string(),
+            wall_clock_ms: round_duration.as_secs_f64() * 1000.0,
+            bytes_sent,
+            message_count,
+            protocol_switches_by_node: switches_by_node,
+        });
+    }
+
+    /// The benchmark history collected so far
+    pub fn results(&self) -> &BenchmarkResults {
+        &self.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::topology::{RoutingPolicy, TopologyKind};
+
+    fn simulation() -> NetworkSimulation {
+        let mut simulation = NetworkSimulation::new();
+        simulation
+            .initialize(6, 0.5, TopologyKind::Mesh, RoutingPolicy::ShortestPath, 7)
+            .unwrap();
+        simulation
+    }
+
+    #[test]
+    fn each_recorded_round_appears_in_results_in_order() {
+        let mut measurements = Measurements::new();
+        let simulation = simulation();
+
+        measurements.record_round(0, "congestion", Duration::from_millis(5), 10, &simulation);
+        measurements.record_round(1, "wireless_interference", Duration::from_millis(8), 10, &simulation);
+
+        let rounds = &measurements.results().rounds;
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].round, 0);
+        assert_eq!(rounds[1].scenario, "wireless_interference");
+    }
+
+    #[test]
+    fn protocol_switches_stay_zero_across_rounds_with_no_protocol_change() {
+        let mut measurements = Measurements::new();
+        let simulation = simulation();
+
+        // No scenario/adaptation has been applied, so every connection's `active_protocol`
+        // stays `None` across both rounds; nothing should ever look like a switch.
+        measurements.record_round(0, "baseline", Duration::from_millis(1), 1, &simulation);
+        measurements.record_round(1, "baseline", Duration::from_millis(1), 1, &simulation);
+
+        assert_eq!(measurements.results().total_protocol_switches(), 0);
+    }
+}
@@ -0,0 +1,475 @@
+//! Pluggable congestion-control window models for simulated links.
+//! Each connection drives a `CongestionControl` implementation from its
+//! measured RTT and packet-loss conditions, producing a congestion window
+//! that the capacity/throughput layer can use to pace traffic.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Default maximum segment size assumed for congestion-window growth, in bytes
+pub const MSS: f64 = 1460.0;
+
+/// A congestion-control algorithm driving a link's congestion window (cwnd)
+pub trait CongestionControl: std::fmt::Debug {
+    /// Acknowledge `bytes` delivered with the observed round-trip-time `rtt`
+    fn on_ack(&mut self, bytes: u64, rtt: Duration);
+    /// Record a loss event
+    fn on_loss(&mut self);
+    /// Current congestion window, in bytes
+    fn cwnd(&self) -> f64;
+    /// Sustainable send rate implied by the current window and RTT, in bits/sec
+    fn send_rate_bps(&self, rtt: Duration) -> f64 {
+        let rtt_secs = rtt.as_secs_f64().max(0.001);
+        (self.cwnd() * 8.0) / rtt_secs
+    }
+    /// Pacing rate the controller actually wants to send at, in bits/sec. Defaults to
+    /// [`Self::send_rate_bps`] for window-based controllers with no independent rate estimate;
+    /// overridden by [`BbrCongestionControl`], which paces off its own `BtlBw` filter instead of
+    /// the passed-in `rtt`.
+    fn pacing_rate_bps(&self, rtt: Duration) -> f64 {
+        self.send_rate_bps(rtt)
+    }
+}
+
+/// New Reno: slow-start doubling of cwnd per RTT until `ssthresh`, then
+/// additive increase of one MSS per RTT, halving cwnd on loss.
+#[derive(Debug, Clone)]
+pub struct NewRenoCongestionControl {
+    cwnd: f64,
+    ssthresh: f64,
+}
+
+impl NewRenoCongestionControl {
+    /// Create a new New Reno controller starting in slow start
+    pub fn new() -> Self {
+        Self {
+            cwnd: MSS,
+            ssthresh: 64.0 * 1024.0,
+        }
+    }
+}
+
+impl CongestionControl for NewRenoCongestionControl {
+    fn on_ack(&mut self, bytes: u64, _rtt: Duration) {
+        if self.cwnd < self.ssthresh {
+            // Slow start: summing one ack's worth of bytes each ack doubles cwnd per RTT
+            self.cwnd += bytes as f64;
+        } else {
+            // Congestion avoidance: this sums to +MSS per RTT across the window's acks
+            self.cwnd += (MSS * MSS) / self.cwnd;
+        }
+    }
+
+    fn on_loss(&mut self) {
+        self.ssthresh = (self.cwnd / 2.0).max(MSS);
+        self.cwnd = self.ssthresh;
+    }
+
+    fn cwnd(&self) -> f64 {
+        self.cwnd
+    }
+}
+
+/// Multiplicative decrease factor applied to cwnd on a CUBIC loss event
+const CUBIC_BETA: f64 = 0.7;
+/// CUBIC window-growth aggressiveness constant
+const CUBIC_C: f64 = 0.4;
+
+/// CUBIC: on loss, sets `w_max` to the pre-loss cwnd and multiplies cwnd by
+/// `beta`, then grows the window as a cubic function of time since the last
+/// congestion event, falling back to a Reno-friendly estimate when it is larger.
+#[derive(Debug, Clone)]
+pub struct CubicCongestionControl {
+    cwnd: f64,
+    ssthresh: f64,
+    w_max: f64,
+    k: f64,
+    time_since_congestion: f64,
+}
+
+impl CubicCongestionControl {
+    /// Create a new CUBIC controller starting in slow start
+    pub fn new() -> Self {
+        Self {
+            cwnd: MSS,
+            ssthresh: 64.0 * 1024.0,
+            w_max: MSS,
+            k: 0.0,
+            time_since_congestion: 0.0,
+        }
+    }
+}
+
+impl CongestionControl for CubicCongestionControl {
+    fn on_ack(&mut self, bytes: u64, rtt: Duration) {
+        let rtt_secs = rtt.as_secs_f64().max(0.001);
+        self.time_since_congestion += rtt_secs;
+
+        if self.cwnd < self.ssthresh {
+            // Slow start, same growth as New Reno
+            self.cwnd += bytes as f64;
+            return;
+        }
+
+        let t = self.time_since_congestion;
+        let cubic_w = CUBIC_C * (t - self.k).powi(3) + self.w_max;
+
+        // Reno-friendly estimate: the window New Reno would reach over the same
+        // time since the congestion event, growing by one MSS per RTT
+        let reno_w = self.w_max * CUBIC_BETA
+            + (3.0 * (1.0 - CUBIC_BETA) / (1.0 + CUBIC_BETA)) * (t / rtt_secs) * MSS;
+
+        self.cwnd = cubic_w.max(reno_w).max(MSS);
+    }
+
+    fn on_loss(&mut self) {
+        self.w_max = self.cwnd;
+        self.cwnd = (self.cwnd * CUBIC_BETA).max(MSS);
+        self.ssthresh = self.cwnd;
+        self.k = (self.w_max * (1.0 - CUBIC_BETA) / CUBIC_C).cbrt();
+        self.time_since_congestion = 0.0;
+    }
+
+    fn cwnd(&self) -> f64 {
+        self.cwnd
+    }
+}
+
+/// Number of round trips [`BbrCongestionControl`]'s `BtlBw`/`RTprop` max/min filters look back
+/// over, per the "~10 RTTs" the model is specified against
+const BBR_FILTER_WINDOW_ROUNDS: usize = 10;
+/// Consecutive Startup rounds with less than 25% `BtlBw` growth before the model declares the
+/// bottleneck found and moves to Drain
+const BBR_STARTUP_ROUNDS_WITHOUT_GROWTH: usize = 3;
+/// Rounds spent in ProbeBW before a periodic ProbeRTT re-measures `RTprop`
+const BBR_PROBE_RTT_INTERVAL_ROUNDS: usize = 10;
+/// Pacing gain applied while in Startup, `2/ln(2)` rounded, the value the BBR paper derives for
+/// doubling delivery rate each round
+const BBR_STARTUP_GAIN: f64 = 2.89;
+/// Cyclic ProbeBW pacing gains: one round each of probing up and draining back down, then six
+/// rounds holding steady
+const BBR_PROBE_BW_GAIN_CYCLE: [f64; 8] = [1.25, 0.75, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+/// cwnd floor during ProbeRTT, in packets, small enough to let a queued bottleneck drain so the
+/// next RTT samples reflect `RTprop` rather than queuing delay
+const BBR_PROBE_RTT_CWND_PACKETS: f64 = 4.0;
+
+/// BBR phase cycle: Startup discovers `BtlBw`, Drain works the queue BBR's own Startup overshoot
+/// built up back down to the bandwidth-delay product, ProbeBW is steady-state operation cycling
+/// pacing gain to probe for more bandwidth, and ProbeRTT periodically shrinks cwnd to re-measure
+/// `RTprop` without a standing queue masking it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BbrPhase {
+    Startup,
+    Drain,
+    ProbeBw,
+    ProbeRtt,
+}
+
+/// BBR: paces off a max-filtered bottleneck-bandwidth (`BtlBw`) and min-filtered round-trip-time
+/// (`RTprop`) estimate instead of reacting to loss directly, so an isolated loss on an otherwise
+/// healthy link doesn't collapse its window the way NewReno/CUBIC's loss-based AIMD does.
+#[derive(Debug, Clone)]
+pub struct BbrCongestionControl {
+    phase: BbrPhase,
+    /// Max-filtered delivery-rate sample over the last [`BBR_FILTER_WINDOW_ROUNDS`] acks, in
+    /// bytes/sec
+    btlbw_bytes_per_sec: f64,
+    /// Min-filtered RTT sample over the last [`BBR_FILTER_WINDOW_ROUNDS`] acks
+    rtprop: Duration,
+    rate_samples: VecDeque<f64>,
+    rtt_samples: VecDeque<Duration>,
+    cwnd: f64,
+    cycle_index: usize,
+    rounds_since_probe_rtt: usize,
+    startup_rounds_without_growth: usize,
+    btlbw_at_last_round: f64,
+}
+
+impl BbrCongestionControl {
+    /// Create a new BBR controller starting in Startup with no bandwidth/RTT samples yet
+    pub fn new() -> Self {
+        Self {
+            phase: BbrPhase::Startup,
+            btlbw_bytes_per_sec: 0.0,
+            rtprop: Duration::from_secs(3600),
+            rate_samples: VecDeque::new(),
+            rtt_samples: VecDeque::new(),
+            cwnd: BBR_PROBE_RTT_CWND_PACKETS * MSS,
+            cycle_index: 0,
+            rounds_since_probe_rtt: 0,
+            startup_rounds_without_growth: 0,
+            btlbw_at_last_round: 0.0,
+        }
+    }
+
+    /// Bandwidth-delay product implied by the current `BtlBw`/`RTprop` filters, the window BBR
+    /// targets in steady state
+    fn bdp(&self) -> f64 {
+        self.btlbw_bytes_per_sec * self.rtprop.as_secs_f64()
+    }
+
+    /// Pacing gain for the current phase/cycle position
+    fn pacing_gain(&self) -> f64 {
+        match self.phase {
+            BbrPhase::Startup => BBR_STARTUP_GAIN,
+            BbrPhase::Drain => 1.0 / BBR_STARTUP_GAIN,
+            BbrPhase::ProbeBw => BBR_PROBE_BW_GAIN_CYCLE[self.cycle_index],
+            BbrPhase::ProbeRtt => 1.0,
+        }
+    }
+
+    fn target_cwnd(&self) -> f64 {
+        let cwnd_gain = match self.phase {
+            BbrPhase::ProbeRtt => return BBR_PROBE_RTT_CWND_PACKETS * MSS,
+            BbrPhase::Startup => BBR_STARTUP_GAIN,
+            // Drain targets the steady-state BDP (gain 1.0), not the 2x-inflated window ProbeBw
+            // runs at -- the Drain exit check below compares `cwnd` against that same 1x BDP, so
+            // using the 2x gain here would make cwnd permanently unable to reach the exit target
+            BbrPhase::Drain => 1.0,
+            BbrPhase::ProbeBw => 2.0,
+        };
+        (cwnd_gain * self.bdp()).max(BBR_PROBE_RTT_CWND_PACKETS * MSS)
+    }
+}
+
+impl CongestionControl for BbrCongestionControl {
+    fn on_ack(&mut self, bytes: u64, rtt: Duration) {
+        let rtt_secs = rtt.as_secs_f64().max(0.001);
+
+        self.rate_samples.push_back(bytes as f64 / rtt_secs);
+        if self.rate_samples.len() > BBR_FILTER_WINDOW_ROUNDS {
+            self.rate_samples.pop_front();
+        }
+        self.btlbw_bytes_per_sec = self.rate_samples.iter().cloned().fold(0.0, f64::max);
+
+        self.rtt_samples.push_back(rtt);
+        if self.rtt_samples.len() > BBR_FILTER_WINDOW_ROUNDS {
+            self.rtt_samples.pop_front();
+        }
+        self.rtprop = self.rtt_samples.iter().cloned().min().unwrap_or(rtt);
+
+        self.rounds_since_probe_rtt += 1;
+
+        match self.phase {
+            BbrPhase::Startup => {
+                if self.btlbw_bytes_per_sec > self.btlbw_at_last_round * 1.25 {
+                    self.startup_rounds_without_growth = 0;
+                } else {
+                    self.startup_rounds_without_growth += 1;
+                }
+                if self.startup_rounds_without_growth >= BBR_STARTUP_ROUNDS_WITHOUT_GROWTH {
+                    self.phase = BbrPhase::Drain;
+                }
+            }
+            BbrPhase::Drain => {
+                if self.cwnd <= self.bdp().max(BBR_PROBE_RTT_CWND_PACKETS * MSS) {
+                    self.phase = BbrPhase::ProbeBw;
+                    self.cycle_index = 0;
+                }
+            }
+            BbrPhase::ProbeBw => {
+                self.cycle_index = (self.cycle_index + 1) % BBR_PROBE_BW_GAIN_CYCLE.len();
+                if self.rounds_since_probe_rtt >= BBR_PROBE_RTT_INTERVAL_ROUNDS {
+                    self.phase = BbrPhase::ProbeRtt;
+                    self.rounds_since_probe_rtt = 0;
+                }
+            }
+            BbrPhase::ProbeRtt => {
+                // One round at the ProbeRTT floor is enough to drain a standing queue and let
+                // the next ack's RTT sample reflect RTprop again
+                self.phase = BbrPhase::ProbeBw;
+            }
+        }
+
+        self.btlbw_at_last_round = self.btlbw_bytes_per_sec;
+        self.cwnd = self.target_cwnd();
+    }
+
+    fn on_loss(&mut self) {
+        // BBR paces off its BtlBw/RTprop model rather than reacting to loss directly; an
+        // isolated loss on an otherwise healthy link leaves cwnd/pacing alone, unlike NewReno's
+        // or CUBIC's AIMD response.
+    }
+
+    fn cwnd(&self) -> f64 {
+        self.cwnd
+    }
+
+    fn send_rate_bps(&self, rtt: Duration) -> f64 {
+        self.pacing_rate_bps(rtt)
+    }
+
+    fn pacing_rate_bps(&self, _rtt: Duration) -> f64 {
+        self.pacing_gain() * self.btlbw_bytes_per_sec * 8.0
+    }
+}
+
+/// Runtime-selectable congestion-control algorithm for a link
+#[derive(Debug, Clone)]
+pub enum CongestionAlgorithm {
+    /// New Reno AIMD window
+    NewReno(NewRenoCongestionControl),
+    /// CUBIC window
+    Cubic(CubicCongestionControl),
+    /// BBR model-based pacing
+    Bbr(BbrCongestionControl),
+}
+
+impl CongestionAlgorithm {
+    /// Select New Reno
+    pub fn new_reno() -> Self {
+        CongestionAlgorithm::NewReno(NewRenoCongestionControl::new())
+    }
+
+    /// Select CUBIC
+    pub fn cubic() -> Self {
+        CongestionAlgorithm::Cubic(CubicCongestionControl::new())
+    }
+
+    /// Select BBR
+    pub fn bbr() -> Self {
+        CongestionAlgorithm::Bbr(BbrCongestionControl::new())
+    }
+
+    /// Short name used to group achieved-throughput reporting in `MetricsCollector`
+    pub fn name(&self) -> &'static str {
+        match self {
+            CongestionAlgorithm::NewReno(_) => "new_reno",
+            CongestionAlgorithm::Cubic(_) => "cubic",
+            CongestionAlgorithm::Bbr(_) => "bbr",
+        }
+    }
+}
+
+impl CongestionControl for CongestionAlgorithm {
+    fn on_ack(&mut self, bytes: u64, rtt: Duration) {
+        match self {
+            CongestionAlgorithm::NewReno(c) => c.on_ack(bytes, rtt),
+            CongestionAlgorithm::Cubic(c) => c.on_ack(bytes, rtt),
+            CongestionAlgorithm::Bbr(c) => c.on_ack(bytes, rtt),
+        }
+    }
+
+    fn on_loss(&mut self) {
+        match self {
+            CongestionAlgorithm::NewReno(c) => c.on_loss(),
+            CongestionAlgorithm::Cubic(c) => c.on_loss(),
+            CongestionAlgorithm::Bbr(c) => c.on_loss(),
+        }
+    }
+
+    fn cwnd(&self) -> f64 {
+        match self {
+            CongestionAlgorithm::NewReno(c) => c.cwnd(),
+            CongestionAlgorithm::Cubic(c) => c.cwnd(),
+            CongestionAlgorithm::Bbr(c) => c.cwnd(),
+        }
+    }
+
+    fn send_rate_bps(&self, rtt: Duration) -> f64 {
+        match self {
+            CongestionAlgorithm::NewReno(c) => c.send_rate_bps(rtt),
+            CongestionAlgorithm::Cubic(c) => c.send_rate_bps(rtt),
+            CongestionAlgorithm::Bbr(c) => c.send_rate_bps(rtt),
+        }
+    }
+
+    fn pacing_rate_bps(&self, rtt: Duration) -> f64 {
+        match self {
+            CongestionAlgorithm::NewReno(c) => c.pacing_rate_bps(rtt),
+            CongestionAlgorithm::Cubic(c) => c.pacing_rate_bps(rtt),
+            CongestionAlgorithm::Bbr(c) => c.pacing_rate_bps(rtt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_reno_halves_cwnd_on_loss() {
+        let mut reno = NewRenoCongestionControl::new();
+        for _ in 0..20 {
+            reno.on_ack(MSS as u64, Duration::from_millis(50));
+        }
+        let before_loss = reno.cwnd();
+        reno.on_loss();
+        assert!((reno.cwnd() - (before_loss / 2.0).max(MSS)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cubic_regrows_toward_w_max_after_loss() {
+        let mut cubic = CubicCongestionControl::new();
+        for _ in 0..50 {
+            cubic.on_ack(MSS as u64, Duration::from_millis(50));
+        }
+        cubic.on_loss();
+        let post_loss_cwnd = cubic.cwnd();
+        assert!(post_loss_cwnd < cubic.w_max);
+
+        for _ in 0..50 {
+            cubic.on_ack(MSS as u64, Duration::from_millis(50));
+        }
+        assert!(cubic.cwnd() > post_loss_cwnd);
+    }
+
+    #[test]
+    fn send_rate_scales_with_cwnd_and_rtt() {
+        let algo = CongestionAlgorithm::new_reno();
+        let rate_fast_rtt = algo.send_rate_bps(Duration::from_millis(10));
+        let rate_slow_rtt = algo.send_rate_bps(Duration::from_millis(100));
+        assert!(rate_fast_rtt > rate_slow_rtt);
+    }
+
+    #[test]
+    fn bbr_estimates_btlbw_from_ack_rate_and_leaves_it_alone_on_loss() {
+        let mut bbr = BbrCongestionControl::new();
+        for _ in 0..20 {
+            bbr.on_ack(MSS as u64, Duration::from_millis(50));
+        }
+        assert!(bbr.btlbw_bytes_per_sec > 0.0);
+
+        let btlbw_before_loss = bbr.btlbw_bytes_per_sec;
+        let cwnd_before_loss = bbr.cwnd();
+        bbr.on_loss();
+        assert_eq!(bbr.btlbw_bytes_per_sec, btlbw_before_loss);
+        assert_eq!(bbr.cwnd(), cwnd_before_loss);
+    }
+
+    #[test]
+    fn bbr_leaves_startup_once_btlbw_stops_growing() {
+        let mut bbr = BbrCongestionControl::new();
+        // A constant-rate link: BtlBw plateaus immediately, so Startup's no-growth counter
+        // should push the model into Drain well within a handful of rounds.
+        for _ in 0..(BBR_STARTUP_ROUNDS_WITHOUT_GROWTH + 1) {
+            bbr.on_ack(MSS as u64, Duration::from_millis(50));
+        }
+        assert_ne!(bbr.phase, BbrPhase::Startup);
+    }
+
+    #[test]
+    fn bbr_pacing_rate_is_zero_before_any_ack() {
+        let bbr = BbrCongestionControl::new();
+        assert_eq!(bbr.pacing_rate_bps(Duration::from_millis(50)), 0.0);
+    }
+
+    #[test]
+    fn bbr_cycles_through_drain_into_probe_bw() {
+        let mut bbr = BbrCongestionControl::new();
+        // A constant-rate, constant-RTT link with a BDP well above the ProbeRTT floor (1,000,000
+        // B/s at 50ms RTT -> 50,000-byte BDP vs. a ~5,840-byte floor), so the Drain exit check
+        // only passes once cwnd actually reaches the 1x-BDP target rather than being satisfied by
+        // the floor clamp regardless of which gain Drain uses.
+        let bytes_per_ack = 50_000u64;
+        let mut saw_probe_bw = false;
+        for _ in 0..2000 {
+            bbr.on_ack(bytes_per_ack, Duration::from_millis(50));
+            if bbr.phase == BbrPhase::ProbeBw {
+                saw_probe_bw = true;
+                break;
+            }
+        }
+        assert!(saw_probe_bw, "BBR should leave Drain and reach ProbeBw once cwnd hits the BDP target");
+    }
+}
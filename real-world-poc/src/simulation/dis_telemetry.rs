@@ -0,0 +1,218 @@
+//! Live IEEE 1278.1 DIS-style PDU streaming over UDP: lets third-party DIS consumers and mesh
+//! viewers observe a running simulation's node/link state without the crate owning a GUI.
+//! Entity-State-like PDUs carry each node's position and active protocol; a custom
+//! Experimental-family link-state PDU carries per-edge metrics, since DIS has no standard PDU
+//! for that. Disabled (`socket` is `None`) whenever no address is configured, so the hot loop
+//! pays nothing beyond a pointer check when live telemetry isn't wanted.
+
+use std::net::UdpSocket;
+
+use super::metrics::ErrorString;
+use super::network::NetworkSimulation;
+
+/// DIS protocol version for IEEE 1278.1-1995
+const PROTOCOL_VERSION: u8 = 6;
+/// "Entity Information/Interaction" protocol family
+const PROTOCOL_FAMILY_ENTITY_INFO: u8 = 1;
+/// "Experimental or Simulator-Specific" protocol family, used for the non-standard link-state PDU
+const PROTOCOL_FAMILY_EXPERIMENTAL: u8 = 255;
+/// Standard Entity State PDU type
+const PDU_TYPE_ENTITY_STATE: u8 = 1;
+/// Vendor-specific PDU type chosen for the link-state PDU; DIS reserves 129-255 for
+/// experimental use and defines no standard PDU for per-edge network metrics
+const PDU_TYPE_LINK_STATE: u8 = 200;
+/// 12-byte PDU header common to every DIS PDU: protocol version, exercise ID, PDU type,
+/// protocol family, timestamp, length, padding
+const PDU_HEADER_LEN: usize = 12;
+/// Entity marking field length: a 1-byte character set identifier followed by an 11-byte
+/// ASCII string, per the DIS Entity State PDU marking field
+const MARKING_LEN: usize = 12;
+
+/// Site/application pair identifying this simulator in the DIS Entity ID, since a single
+/// process always plays both roles here
+const SITE_ID: u16 = 1;
+const APPLICATION_ID: u16 = 1;
+
+fn write_pdu_header(buf: &mut Vec<u8>, pdu_type: u8, protocol_family: u8, exercise_id: u8, timestamp: u32, body_len: usize) {
+    buf.push(PROTOCOL_VERSION);
+    buf.push(exercise_id);
+    buf.push(pdu_type);
+    buf.push(protocol_family);
+    buf.extend_from_slice(&timestamp.to_be_bytes());
+    buf.extend_from_slice(&((PDU_HEADER_LEN + body_len) as u16).to_be_bytes());
+    buf.extend_from_slice(&0u16.to_be_bytes()); // padding
+}
+
+/// ASCII character set identifier for the DIS Entity Marking field
+const MARKING_CHARSET_ASCII: u8 = 1;
+
+fn write_marking(buf: &mut Vec<u8>, text: &str) {
+    buf.push(MARKING_CHARSET_ASCII);
+    let mut chars = [0u8; MARKING_LEN - 1];
+    for (slot, byte) in chars.iter_mut().zip(text.as_bytes()) {
+        *slot = *byte;
+    }
+    buf.extend_from_slice(&chars);
+}
+
+/// Encode a single node as an Entity State PDU: DIS entity ID (site/application fixed, node ID
+/// as the entity number), its simulated `(x, y)` location (z pinned to 0.0), and its currently
+/// active protocol packed into the standard marking field
+fn encode_entity_state_pdu(exercise_id: u8, timestamp: u32, entity_id: u16, x: f64, y: f64, active_protocol: Option<&str>) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&SITE_ID.to_be_bytes());
+    body.extend_from_slice(&APPLICATION_ID.to_be_bytes());
+    body.extend_from_slice(&entity_id.to_be_bytes());
+    body.extend_from_slice(&x.to_be_bytes());
+    body.extend_from_slice(&y.to_be_bytes());
+    body.extend_from_slice(&0.0f64.to_be_bytes());
+    write_marking(&mut body, active_protocol.unwrap_or(""));
+
+    let mut pdu = Vec::with_capacity(PDU_HEADER_LEN + body.len());
+    write_pdu_header(&mut pdu, PDU_TYPE_ENTITY_STATE, PROTOCOL_FAMILY_ENTITY_INFO, exercise_id, timestamp, body.len());
+    pdu.extend_from_slice(&body);
+    pdu
+}
+
+/// Encode a single link as a custom link-state PDU: source/dest entity IDs and the link's
+/// current latency/bandwidth/loss/jitter
+fn encode_link_state_pdu(exercise_id: u8, timestamp: u32, source_entity_id: u16, dest_entity_id: u16, latency_ms: f64, bandwidth_kbps: f64, packet_loss: f64, jitter_ms: f64) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&source_entity_id.to_be_bytes());
+    body.extend_from_slice(&dest_entity_id.to_be_bytes());
+    body.extend_from_slice(&latency_ms.to_be_bytes());
+    body.extend_from_slice(&bandwidth_kbps.to_be_bytes());
+    body.extend_from_slice(&packet_loss.to_be_bytes());
+    body.extend_from_slice(&jitter_ms.to_be_bytes());
+
+    let mut pdu = Vec::with_capacity(PDU_HEADER_LEN + body.len());
+    write_pdu_header(&mut pdu, PDU_TYPE_LINK_STATE, PROTOCOL_FAMILY_EXPERIMENTAL, exercise_id, timestamp, body.len());
+    pdu.extend_from_slice(&body);
+    pdu
+}
+
+/// Streams a running simulation's node/link state as DIS-style PDUs over UDP to a configured
+/// address; a no-op whenever no address was configured, keeping `send_snapshot` cheap on the
+/// hot loop when live telemetry isn't wanted
+pub struct DisTelemetryEmitter {
+    socket: Option<UdpSocket>,
+    exercise_id: u8,
+    next_timestamp: u32,
+}
+
+impl DisTelemetryEmitter {
+    /// A disabled emitter: every `send_snapshot` call is a no-op
+    pub fn disabled() -> Self {
+        Self { socket: None, exercise_id: 1, next_timestamp: 0 }
+    }
+
+    /// Build an emitter, binding an ephemeral local UDP socket and connecting it to `addr` so
+    /// later `send` calls don't need to re-resolve it; `addr` of `None` disables telemetry
+    pub fn new(addr: Option<&str>) -> Result<Self, ErrorString> {
+        let socket = match addr {
+            Some(addr) => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.connect(addr).map_err(|e| {
+                    ErrorString(format!("Failed to connect DIS telemetry socket to {}: {}", addr, e))
+                })?;
+                Some(socket)
+            }
+            None => None,
+        };
+
+        Ok(Self { socket, exercise_id: 1, next_timestamp: 0 })
+    }
+
+    /// Broadcast an Entity State PDU for every node and a link-state PDU for every topology
+    /// link in `network`; does nothing when telemetry is disabled
+    pub fn send_snapshot(&mut self, network: &NetworkSimulation) -> Result<(), ErrorString> {
+        let socket = match &self.socket {
+            Some(socket) => socket,
+            None => return Ok(()),
+        };
+
+        let timestamp = self.next_timestamp;
+        self.next_timestamp = self.next_timestamp.wrapping_add(1);
+
+        let mut protocol_by_node: std::collections::HashMap<usize, String> = std::collections::HashMap::new();
+        for conn in network.get_connections() {
+            if let Some(protocol) = &conn.active_protocol {
+                for &node_id in &conn.path {
+                    protocol_by_node.entry(node_id).or_insert_with(|| protocol.clone());
+                }
+            }
+        }
+
+        for (&id, node) in network.get_nodes() {
+            let (x, y) = node.location();
+            let pdu = encode_entity_state_pdu(self.exercise_id, timestamp, id as u16, x, y, protocol_by_node.get(&id).map(String::as_str));
+            Self::send(socket, &pdu)?;
+        }
+
+        for ((source, dest), link) in network.topology_links() {
+            let pdu = encode_link_state_pdu(
+                self.exercise_id,
+                timestamp,
+                source as u16,
+                dest as u16,
+                link.latency_ms,
+                link.bandwidth_kbps,
+                link.packet_loss,
+                link.jitter_ms,
+            );
+            Self::send(socket, &pdu)?;
+        }
+
+        Ok(())
+    }
+
+    fn send(socket: &UdpSocket, pdu: &[u8]) -> Result<(), ErrorString> {
+        // A dropped or refused datagram shouldn't abort the simulation: the transport is
+        // best-effort, same as any other DIS exercise on a shared network.
+        let _ = socket.send(pdu);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entity_state_pdu_has_correct_header_and_length() {
+        let pdu = encode_entity_state_pdu(1, 42, 7, 10.0, 20.0, Some("quic"));
+
+        assert_eq!(pdu[0], PROTOCOL_VERSION);
+        assert_eq!(pdu[1], 1); // exercise_id
+        assert_eq!(pdu[2], PDU_TYPE_ENTITY_STATE);
+        assert_eq!(pdu[3], PROTOCOL_FAMILY_ENTITY_INFO);
+        assert_eq!(u32::from_be_bytes(pdu[4..8].try_into().unwrap()), 42);
+        assert_eq!(u16::from_be_bytes(pdu[8..10].try_into().unwrap()) as usize, pdu.len());
+
+        let entity_id = u16::from_be_bytes(pdu[PDU_HEADER_LEN + 4..PDU_HEADER_LEN + 6].try_into().unwrap());
+        assert_eq!(entity_id, 7);
+    }
+
+    #[test]
+    fn link_state_pdu_round_trips_metrics() {
+        let pdu = encode_link_state_pdu(1, 0, 3, 4, 12.5, 1000.0, 0.01, 2.0);
+
+        assert_eq!(pdu[2], PDU_TYPE_LINK_STATE);
+        assert_eq!(pdu[3], PROTOCOL_FAMILY_EXPERIMENTAL);
+
+        let body = &pdu[PDU_HEADER_LEN..];
+        let source = u16::from_be_bytes(body[0..2].try_into().unwrap());
+        let dest = u16::from_be_bytes(body[2..4].try_into().unwrap());
+        let latency_ms = f64::from_be_bytes(body[4..12].try_into().unwrap());
+        assert_eq!(source, 3);
+        assert_eq!(dest, 4);
+        assert_eq!(latency_ms, 12.5);
+    }
+
+    #[test]
+    fn disabled_emitter_is_a_no_op() {
+        let mut emitter = DisTelemetryEmitter::new(None).unwrap();
+        let network = NetworkSimulation::new();
+        assert!(emitter.send_snapshot(&network).is_ok());
+    }
+}
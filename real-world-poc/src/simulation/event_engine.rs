@@ -0,0 +1,119 @@
+//! Discrete-event core driving [`super::NetworkSimulation::run_event_driven`]: a priority queue
+//! of per-connection update events keyed by simulated time, not wall-clock time. Events sharing
+//! a timestamp form a "wavefront" — since nothing in it can have been caused by anything else
+//! still pending (everything else is scheduled strictly later), a wavefront is safe to dispatch
+//! across a worker pool in any order. This is what actually decouples `duration_secs` from real
+//! elapsed time: the old wall-clock loop paced itself off [`std::time::Instant::elapsed`], so a
+//! "120 second" run took 120 real seconds; this one advances the simulated clock tick by tick and
+//! finishes as fast as the CPU allows.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// One connection's next scheduled update. Ordered by `at_ms` then `connection_idx`, so two
+/// events at the same simulated time always pop in the same relative order regardless of
+/// insertion order or how many workers end up processing the wavefront.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub at_ms: u64,
+    pub connection_idx: usize,
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.at_ms.cmp(&other.at_ms).then_with(|| self.connection_idx.cmp(&other.connection_idx))
+    }
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Priority queue of pending per-connection update events, keyed by simulated time
+#[derive(Default)]
+pub struct EventQueue {
+    heap: BinaryHeap<Reverse<Event>>,
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        Self { heap: BinaryHeap::new() }
+    }
+
+    pub fn schedule(&mut self, event: Event) {
+        self.heap.push(Reverse(event));
+    }
+
+    /// Earliest pending event's time, or `None` if the queue is empty
+    pub fn next_time(&self) -> Option<u64> {
+        self.heap.peek().map(|Reverse(event)| event.at_ms)
+    }
+
+    /// Pop every event sharing the earliest pending time: the next wavefront safe to process in
+    /// parallel, since the safe-time barrier guarantees nothing still queued can affect it.
+    pub fn drain_wavefront(&mut self) -> Vec<Event> {
+        let Some(time) = self.next_time() else { return Vec::new() };
+        let mut wavefront = Vec::new();
+        while let Some(Reverse(event)) = self.heap.peek().copied() {
+            if event.at_ms != time {
+                break;
+            }
+            wavefront.push(event);
+            self.heap.pop();
+        }
+        wavefront
+    }
+}
+
+/// Derive a connection's per-wavefront RNG seed from the wavefront's own seed and the
+/// connection's index, using a splitmix64-style mix. Depending only on `(wavefront_seed,
+/// connection_idx)` — never on which worker thread or in what order a connection was processed
+/// — is what keeps a run's outcome identical across different `worker_count`s.
+pub fn derive_tick_seed(wavefront_seed: u64, connection_idx: usize) -> u64 {
+    let mut z = wavefront_seed.wrapping_add((connection_idx as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wavefront_only_drains_the_earliest_timestamp() {
+        let mut queue = EventQueue::new();
+        queue.schedule(Event { at_ms: 200, connection_idx: 0 });
+        queue.schedule(Event { at_ms: 100, connection_idx: 1 });
+        queue.schedule(Event { at_ms: 100, connection_idx: 2 });
+
+        let wavefront = queue.drain_wavefront();
+        assert_eq!(wavefront.len(), 2);
+        assert!(wavefront.iter().all(|event| event.at_ms == 100));
+        assert_eq!(queue.next_time(), Some(200));
+    }
+
+    #[test]
+    fn events_at_the_same_time_order_by_connection_idx() {
+        let mut queue = EventQueue::new();
+        queue.schedule(Event { at_ms: 50, connection_idx: 5 });
+        queue.schedule(Event { at_ms: 50, connection_idx: 1 });
+        queue.schedule(Event { at_ms: 50, connection_idx: 3 });
+
+        let wavefront = queue.drain_wavefront();
+        let indices: Vec<usize> = wavefront.iter().map(|event| event.connection_idx).collect();
+        assert_eq!(indices, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn derived_seeds_are_stable_regardless_of_how_theyre_grouped() {
+        // The same (wavefront_seed, connection_idx) pair must mix to the same seed whether it's
+        // looked up first or last in a batch, since that's the whole determinism guarantee.
+        let a = derive_tick_seed(42, 7);
+        let b = derive_tick_seed(42, 7);
+        assert_eq!(a, b);
+        assert_ne!(derive_tick_seed(42, 7), derive_tick_seed(42, 8));
+    }
+}
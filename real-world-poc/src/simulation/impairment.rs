@@ -0,0 +1,211 @@
+//! Pluggable per-packet impairment layer: lets a run inject specific, deterministic hardship
+//! (drops, delay, duplication) onto a link's traffic, instead of only varying aggregate
+//! conditions through `connection_density` and scenario knobs. Stored as an optional
+//! `Box<dyn Impairment>` on [`super::NetworkSimulation`], so a run with none configured pays no
+//! cost beyond the `None` check.
+
+use std::collections::HashSet;
+use std::f64::consts::PI;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Identifies the link an [`Impairment`] is being asked to interfere with: the same
+/// `(source_id, dest_id)` pair used to key `NetworkSimulation::connection_metrics`
+pub type LinkId = (usize, usize);
+
+/// A single simulated packet crossing a link, carrying just enough for an impairment to make a
+/// decision against the tick-based conditions model
+#[derive(Debug, Clone, Copy)]
+pub struct Packet {
+    pub size_bytes: f64,
+    pub latency_ms: f64,
+}
+
+/// Outcome of an [`Impairment`] deciding what happens to a packet crossing a link
+#[derive(Debug, Clone)]
+pub enum PacketBehavior {
+    /// The packet is lost
+    Drop,
+    /// The packet arrives unmodified
+    Deliver(Packet),
+    /// The packet arrives, but only after an extra delay
+    Delay(Packet, Duration),
+    /// The packet arrives alongside a duplicate of itself
+    Duplicate(Packet),
+}
+
+/// A pluggable impairment applied to packets crossing a link
+pub trait Impairment {
+    fn interfere(&mut self, link: &LinkId, packet: Packet) -> PacketBehavior;
+}
+
+/// Drops packets at a fixed probability, independent of link identity
+pub struct FixedLossRate {
+    drop_probability: f64,
+    rng: StdRng,
+}
+
+impl FixedLossRate {
+    pub fn new(drop_probability: f64, seed: u64) -> Self {
+        Self { drop_probability: drop_probability.clamp(0.0, 1.0), rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Impairment for FixedLossRate {
+    fn interfere(&mut self, _link: &LinkId, packet: Packet) -> PacketBehavior {
+        if self.rng.gen_bool(self.drop_probability) {
+            PacketBehavior::Drop
+        } else {
+            PacketBehavior::Deliver(packet)
+        }
+    }
+}
+
+/// Distribution a [`LatencyJitter`] impairment draws its extra delay from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum JitterDistribution {
+    Uniform { min_ms: f64, max_ms: f64 },
+    Normal { mean_ms: f64, std_dev_ms: f64 },
+}
+
+/// Adds an extra delay sampled from a [`JitterDistribution`] to every packet on a link
+pub struct LatencyJitter {
+    distribution: JitterDistribution,
+    rng: StdRng,
+}
+
+impl LatencyJitter {
+    pub fn new(distribution: JitterDistribution, seed: u64) -> Self {
+        Self { distribution, rng: StdRng::seed_from_u64(seed) }
+    }
+
+    fn sample_delay_ms(&mut self) -> f64 {
+        match self.distribution {
+            JitterDistribution::Uniform { min_ms, max_ms } => {
+                self.rng.gen_range(min_ms..max_ms.max(min_ms + f64::EPSILON))
+            }
+            JitterDistribution::Normal { mean_ms, std_dev_ms } => {
+                // Box-Muller transform, to avoid pulling in a distributions crate for one draw
+                let u1: f64 = self.rng.gen_range(f64::EPSILON..1.0);
+                let u2: f64 = self.rng.gen_range(0.0..1.0);
+                let z0 = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+                (mean_ms + std_dev_ms * z0).max(0.0)
+            }
+        }
+    }
+}
+
+impl Impairment for LatencyJitter {
+    fn interfere(&mut self, _link: &LinkId, packet: Packet) -> PacketBehavior {
+        let delay_ms = self.sample_delay_ms();
+        PacketBehavior::Delay(packet, Duration::from_secs_f64((delay_ms / 1000.0).max(0.0)))
+    }
+}
+
+/// Severs a fixed set of links for a window of steps, dropping every packet they carry while
+/// the window is active. Expressed in the impairment's own call count rather than wall-clock
+/// time, so it stays deterministic under the tick-driven replay check.
+pub struct PartitionWindow {
+    severed_links: HashSet<LinkId>,
+    start_step: u64,
+    end_step: u64,
+    step: u64,
+}
+
+impl PartitionWindow {
+    pub fn new(severed_links: HashSet<LinkId>, start_step: u64, end_step: u64) -> Self {
+        Self { severed_links, start_step, end_step, step: 0 }
+    }
+
+    fn normalized(link: &LinkId) -> LinkId {
+        if link.0 < link.1 { *link } else { (link.1, link.0) }
+    }
+}
+
+impl Impairment for PartitionWindow {
+    fn interfere(&mut self, link: &LinkId, packet: Packet) -> PacketBehavior {
+        self.step += 1;
+        let active = self.step >= self.start_step && self.step <= self.end_step;
+        if active && self.severed_links.contains(&Self::normalized(link)) {
+            PacketBehavior::Drop
+        } else {
+            PacketBehavior::Deliver(packet)
+        }
+    }
+}
+
+/// Declarative description of an [`Impairment`] to install, resolved into a boxed trait object
+/// by [`super::LargeScaleSimulator::initialize`] — kept out of the trait object itself so
+/// `SimulationConfig` stays a plain, inspectable value rather than carrying boxed state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ImpairmentConfig {
+    FixedLossRate { drop_probability: f64, seed: u64 },
+    LatencyJitter { distribution: JitterDistribution, seed: u64 },
+    PartitionWindow { severed_links: Vec<LinkId>, start_step: u64, end_step: u64 },
+}
+
+impl ImpairmentConfig {
+    pub fn build(self) -> Box<dyn Impairment> {
+        match self {
+            ImpairmentConfig::FixedLossRate { drop_probability, seed } => {
+                Box::new(FixedLossRate::new(drop_probability, seed))
+            }
+            ImpairmentConfig::LatencyJitter { distribution, seed } => {
+                Box::new(LatencyJitter::new(distribution, seed))
+            }
+            ImpairmentConfig::PartitionWindow { severed_links, start_step, end_step } => {
+                Box::new(PartitionWindow::new(severed_links.into_iter().collect(), start_step, end_step))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet() -> Packet {
+        Packet { size_bytes: 1460.0, latency_ms: 50.0 }
+    }
+
+    #[test]
+    fn fixed_loss_rate_of_one_always_drops() {
+        let mut impairment = FixedLossRate::new(1.0, 7);
+        assert!(matches!(impairment.interfere(&(0, 1), packet()), PacketBehavior::Drop));
+    }
+
+    #[test]
+    fn fixed_loss_rate_of_zero_never_drops() {
+        let mut impairment = FixedLossRate::new(0.0, 7);
+        for _ in 0..20 {
+            assert!(matches!(impairment.interfere(&(0, 1), packet()), PacketBehavior::Deliver(_)));
+        }
+    }
+
+    #[test]
+    fn latency_jitter_adds_delay_within_the_uniform_range() {
+        let mut impairment =
+            LatencyJitter::new(JitterDistribution::Uniform { min_ms: 10.0, max_ms: 20.0 }, 7);
+        match impairment.interfere(&(0, 1), packet()) {
+            PacketBehavior::Delay(_, extra) => {
+                let extra_ms = extra.as_secs_f64() * 1000.0;
+                assert!((10.0..20.0).contains(&extra_ms));
+            }
+            other => panic!("expected a delay, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn partition_window_severs_only_the_configured_links_during_the_window() {
+        let mut severed = HashSet::new();
+        severed.insert((0, 1));
+        let mut impairment = PartitionWindow::new(severed, 2, 3);
+
+        assert!(matches!(impairment.interfere(&(0, 1), packet()), PacketBehavior::Deliver(_))); // step 1: before window
+        assert!(matches!(impairment.interfere(&(0, 1), packet()), PacketBehavior::Drop)); // step 2: inside window
+        assert!(matches!(impairment.interfere(&(2, 3), packet()), PacketBehavior::Deliver(_))); // step 3: different link
+    }
+}
@@ -0,0 +1,238 @@
+//! Link-level emulation of a constrained path between two `SimulationNode`s.
+//! Models the same knobs as standard netem scenarios (delay, bandwidth ceiling,
+//! queue depth, drop rate) so bandwidth/delay/queue experiments are
+//! reproducible across the whole topology, not just approximated per-connection.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use super::nodes::SimulationNode;
+
+/// Propagation speed assumed across the simulation's arbitrary distance units,
+/// in milliseconds of one-way delay per unit of distance
+const PROPAGATION_MS_PER_UNIT: f64 = 0.05;
+/// Minimum one-way propagation delay, regardless of distance
+const MIN_PROPAGATION_DELAY: Duration = Duration::from_micros(200);
+/// Number of recent deliveries kept for the latency/goodput readouts
+const SAMPLE_HISTORY: usize = 64;
+
+/// A message in flight on a `ConstrainedLink`, scheduled to arrive once both
+/// its propagation delay and its share of the bandwidth have elapsed
+struct InFlightMessage {
+    size_bytes: usize,
+    enqueued_at: Instant,
+    arrives_at: Instant,
+}
+
+/// Emulates a constrained link between two nodes: messages are serialized no
+/// faster than the link's bandwidth allows, queued up to a finite depth, and
+/// dropped either on queue overflow or by a configured random loss rate.
+pub struct ConstrainedLink {
+    /// One-way propagation delay, derived from the endpoints' distance
+    one_way_delay: Duration,
+    /// Bandwidth ceiling, in bytes/sec
+    bandwidth_bytes_per_sec: f64,
+    /// Maximum number of messages the queue can hold before it overflows
+    max_queue_depth: usize,
+    /// Probability (0.0-1.0) that an enqueued message is dropped outright
+    drop_rate: f64,
+    /// Messages queued or in flight, in arrival order
+    queue: VecDeque<InFlightMessage>,
+    /// Time at which the link becomes free to start serializing the next message
+    next_free_at: Instant,
+    /// Recent end-to-end latencies of delivered messages, in milliseconds
+    latency_samples: VecDeque<f64>,
+    /// Recent delivered message sizes with their delivery time, for goodput
+    delivered: VecDeque<(Instant, usize)>,
+    /// Total messages dropped (queue overflow or random loss)
+    dropped_count: u64,
+}
+
+impl ConstrainedLink {
+    /// Build a constrained link between two nodes: the base propagation delay
+    /// comes from `SimulationNode::distance_to`, and the bandwidth ceiling is
+    /// the slower of the two endpoints' link capacities.
+    pub fn from_nodes(
+        source: &SimulationNode,
+        dest: &SimulationNode,
+        max_queue_depth: usize,
+        drop_rate: f64,
+        now: Instant,
+    ) -> Self {
+        let distance = source.distance_to(dest);
+        let one_way_delay = Duration::from_secs_f64(distance * PROPAGATION_MS_PER_UNIT / 1000.0)
+            .max(MIN_PROPAGATION_DELAY);
+        let bandwidth_bytes_per_sec =
+            source.capacity_bps().min(dest.capacity_bps()) as f64 / 8.0;
+
+        Self {
+            one_way_delay,
+            bandwidth_bytes_per_sec,
+            max_queue_depth,
+            drop_rate: drop_rate.clamp(0.0, 1.0),
+            queue: VecDeque::new(),
+            next_free_at: now,
+            latency_samples: VecDeque::new(),
+            delivered: VecDeque::new(),
+            dropped_count: 0,
+        }
+    }
+
+    /// Re-derive the one-way propagation delay from the endpoints' current
+    /// positions, so mobile nodes moving via `update_position` shift link
+    /// latency over time without rebuilding the link.
+    pub fn refresh_distance(&mut self, source: &SimulationNode, dest: &SimulationNode) {
+        let distance = source.distance_to(dest);
+        self.one_way_delay = Duration::from_secs_f64(distance * PROPAGATION_MS_PER_UNIT / 1000.0)
+            .max(MIN_PROPAGATION_DELAY);
+    }
+
+    /// Offer a message to the link. Drops it (and returns `false`) if the
+    /// queue is full or the configured loss probability fires; otherwise
+    /// schedules its arrival and returns `true`.
+    pub fn try_send(&mut self, size_bytes: usize, now: Instant, rng: &mut impl Rng) -> bool {
+        if self.queue.len() >= self.max_queue_depth || rng.gen_bool(self.drop_rate) {
+            self.dropped_count += 1;
+            return false;
+        }
+
+        // Serialize no faster than the bandwidth ceiling allows, queuing behind
+        // whatever is already being sent
+        let serialization_time =
+            Duration::from_secs_f64(size_bytes as f64 / self.bandwidth_bytes_per_sec.max(1.0));
+        let send_start = self.next_free_at.max(now);
+        self.next_free_at = send_start + serialization_time;
+
+        self.queue.push_back(InFlightMessage {
+            size_bytes,
+            enqueued_at: now,
+            arrives_at: self.next_free_at + self.one_way_delay,
+        });
+
+        true
+    }
+
+    /// Release any messages whose arrival time has passed, recording their
+    /// end-to-end latency and goodput contribution
+    pub fn poll_delivered(&mut self, now: Instant) -> usize {
+        let mut delivered_count = 0;
+
+        while let Some(front) = self.queue.front() {
+            if front.arrives_at > now {
+                break;
+            }
+
+            let message = self.queue.pop_front().unwrap();
+            let latency_ms = message.arrives_at.duration_since(message.enqueued_at).as_secs_f64() * 1000.0;
+
+            self.latency_samples.push_back(latency_ms);
+            if self.latency_samples.len() > SAMPLE_HISTORY {
+                self.latency_samples.pop_front();
+            }
+
+            self.delivered.push_back((message.arrives_at, message.size_bytes));
+            if self.delivered.len() > SAMPLE_HISTORY {
+                self.delivered.pop_front();
+            }
+
+            delivered_count += 1;
+        }
+
+        delivered_count
+    }
+
+    /// Measured end-to-end latency over recently delivered messages, in
+    /// milliseconds, or `None` if nothing has been delivered yet
+    pub fn measured_latency_ms(&self) -> Option<f64> {
+        if self.latency_samples.is_empty() {
+            return None;
+        }
+        Some(self.latency_samples.iter().sum::<f64>() / self.latency_samples.len() as f64)
+    }
+
+    /// Achieved goodput over the trailing `window`, in bits/sec, based on
+    /// recently delivered message sizes
+    pub fn achieved_goodput_bps(&self, now: Instant, window: Duration) -> f64 {
+        let cutoff = now.checked_sub(window).unwrap_or(now);
+        let bytes: usize = self
+            .delivered
+            .iter()
+            .filter(|(delivered_at, _)| *delivered_at >= cutoff)
+            .map(|(_, size)| *size)
+            .sum();
+
+        bytes as f64 * 8.0 / window.as_secs_f64().max(0.001)
+    }
+
+    /// Number of messages dropped so far, either by queue overflow or by the
+    /// configured random loss rate
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count
+    }
+
+    /// Number of messages currently queued or in flight
+    pub fn queue_len(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::nodes::NodeType;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn delivers_messages_after_bandwidth_and_propagation_delay() {
+        let mut source = SimulationNode::new(0, "a".to_string(), NodeType::Datacenter);
+        let mut dest = SimulationNode::new(1, "b".to_string(), NodeType::Datacenter);
+        source.set_location(0.0, 0.0);
+        dest.set_location(0.0, 0.0);
+        source.set_capacity_bps(8_000); // 1000 bytes/sec
+        dest.set_capacity_bps(8_000);
+
+        let now = Instant::now();
+        let mut link = ConstrainedLink::from_nodes(&source, &dest, 10, 0.0, now);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert!(link.try_send(1000, now, &mut rng));
+        assert_eq!(link.poll_delivered(now), 0, "message should not arrive instantly");
+
+        let later = now + Duration::from_secs(2);
+        assert_eq!(link.poll_delivered(later), 1);
+        assert!(link.measured_latency_ms().unwrap() >= 1000.0);
+    }
+
+    #[test]
+    fn drops_messages_once_queue_is_full() {
+        let source = SimulationNode::new(0, "a".to_string(), NodeType::Datacenter);
+        let dest = SimulationNode::new(1, "b".to_string(), NodeType::Datacenter);
+
+        let now = Instant::now();
+        let mut link = ConstrainedLink::from_nodes(&source, &dest, 1, 0.0, now);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert!(link.try_send(100, now, &mut rng));
+        assert!(!link.try_send(100, now, &mut rng));
+        assert_eq!(link.dropped_count(), 1);
+    }
+
+    #[test]
+    fn mobile_node_movement_shifts_link_latency() {
+        let mut source = SimulationNode::new(0, "a".to_string(), NodeType::MobileDevice);
+        let mut dest = SimulationNode::new(1, "b".to_string(), NodeType::Datacenter);
+        source.set_location(0.0, 0.0);
+        dest.set_location(0.0, 0.0);
+
+        let now = Instant::now();
+        let mut link = ConstrainedLink::from_nodes(&source, &dest, 10, 0.0, now);
+        let close_delay = link.one_way_delay;
+
+        source.set_location(900.0, 900.0);
+        link.refresh_distance(&source, &dest);
+        assert!(link.one_way_delay > close_delay);
+    }
+}
@@ -0,0 +1,192 @@
+//! Ground-truth packet-loss detection, modeled on QUIC's loss-detection
+//! algorithm (RFC 9002): rather than trusting whatever `packet_loss` rate the
+//! simulation happens to hand over, a connection's sent and acknowledged
+//! packet events are tracked directly and a packet is only declared lost once
+//! a later acknowledgment proves it is either too far behind in packet number
+//! or too old in time. Packets that turn up after being presumed lost are
+//! reclassified as reordering rather than loss.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Packets this far (or more) behind an acked packet number are presumed lost
+const PACKET_THRESHOLD: u64 = 3;
+/// Multiplier applied to the RTT estimate to get the loss time threshold
+const TIME_THRESHOLD_MULTIPLIER: f64 = 1.125;
+
+/// Bookkeeping kept for a sent packet until it is acked or declared lost
+struct SentPacketInfo {
+    send_time: Duration,
+    #[allow(dead_code)]
+    size_bytes: usize,
+}
+
+/// Per-connection ground-truth loss detector. Tracks outstanding sent packets
+/// in a `send_time`-ordered map, keyed by packet number, and resolves each one
+/// to acked, lost, or (if a presumed-lost packet is later acked) reordered.
+pub struct LossDetector {
+    /// Packets sent but not yet acked or declared lost
+    outstanding: BTreeMap<u64, SentPacketInfo>,
+    /// Packets declared lost, kept around so a later ack can reclassify them
+    presumed_lost: BTreeMap<u64, SentPacketInfo>,
+    /// Running smoothed RTT: `srtt = 7/8*srtt + 1/8*latest`
+    smoothed_rtt: Option<Duration>,
+    /// Next packet number to hand out via `next_packet_number`
+    next_packet_number: u64,
+    sent_count: u64,
+    lost_count: u64,
+    reordered_count: u64,
+}
+
+impl LossDetector {
+    /// Create a new, empty loss detector for one connection
+    pub fn new() -> Self {
+        Self {
+            outstanding: BTreeMap::new(),
+            presumed_lost: BTreeMap::new(),
+            smoothed_rtt: None,
+            next_packet_number: 0,
+            sent_count: 0,
+            lost_count: 0,
+            reordered_count: 0,
+        }
+    }
+
+    /// Allocate the next packet number for this connection
+    pub fn next_packet_number(&mut self) -> u64 {
+        let pn = self.next_packet_number;
+        self.next_packet_number += 1;
+        pn
+    }
+
+    /// Record a freshly sent packet
+    pub fn on_packet_sent(&mut self, packet_number: u64, send_time: Duration, size_bytes: usize) {
+        self.outstanding
+            .insert(packet_number, SentPacketInfo { send_time, size_bytes });
+        self.sent_count += 1;
+    }
+
+    /// Record an acknowledgment carrying the largest acked packet number seen
+    /// so far and its receive time. Updates the smoothed RTT and declares any
+    /// now-provably-lost outstanding packets lost, reclassifying as reordering
+    /// any packet that was previously presumed lost.
+    pub fn on_ack_received(&mut self, largest_acked: u64, receive_time: Duration) {
+        let acked_info = if let Some(info) = self.outstanding.remove(&largest_acked) {
+            info
+        } else if let Some(info) = self.presumed_lost.remove(&largest_acked) {
+            self.lost_count -= 1;
+            self.reordered_count += 1;
+            info
+        } else {
+            // Unknown or duplicate acknowledgment
+            return;
+        };
+
+        let latest_rtt = receive_time.saturating_sub(acked_info.send_time);
+        let smoothed_rtt = match self.smoothed_rtt {
+            Some(srtt) => Duration::from_secs_f64(srtt.as_secs_f64() * 7.0 / 8.0 + latest_rtt.as_secs_f64() / 8.0),
+            None => latest_rtt,
+        };
+        self.smoothed_rtt = Some(smoothed_rtt);
+
+        let time_threshold =
+            Duration::from_secs_f64(TIME_THRESHOLD_MULTIPLIER * smoothed_rtt.max(latest_rtt).as_secs_f64());
+        let largest_acked_send_time = acked_info.send_time;
+
+        let newly_lost: Vec<u64> = self
+            .outstanding
+            .range(..largest_acked)
+            .filter(|(&pn, info)| {
+                pn + PACKET_THRESHOLD <= largest_acked
+                    || info.send_time + time_threshold <= largest_acked_send_time
+            })
+            .map(|(&pn, _)| pn)
+            .collect();
+
+        for pn in newly_lost {
+            if let Some(info) = self.outstanding.remove(&pn) {
+                self.presumed_lost.insert(pn, info);
+                self.lost_count += 1;
+            }
+        }
+    }
+
+    /// Ground-truth loss rate over everything sent so far: lost / sent
+    pub fn loss_rate(&self) -> f64 {
+        if self.sent_count == 0 {
+            0.0
+        } else {
+            self.lost_count as f64 / self.sent_count as f64
+        }
+    }
+
+    /// Fraction of sent packets that were presumed lost but later turned up,
+    /// i.e. reordered rather than actually lost
+    pub fn reorder_rate(&self) -> f64 {
+        if self.sent_count == 0 {
+            0.0
+        } else {
+            self.reordered_count as f64 / self.sent_count as f64
+        }
+    }
+
+    /// Current smoothed RTT estimate, or `None` until the first ack arrives
+    pub fn smoothed_rtt(&self) -> Option<Duration> {
+        self.smoothed_rtt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packet_threshold_declares_far_behind_packets_lost() {
+        let mut detector = LossDetector::new();
+
+        for pn in 0..5 {
+            detector.on_packet_sent(pn, Duration::from_millis(pn * 10), 1200);
+        }
+
+        // Ack packet 4 only; packets 0 and 1 are >= PACKET_THRESHOLD behind and
+        // should be declared lost, packets 2 and 3 should still be outstanding
+        detector.on_ack_received(4, Duration::from_millis(100));
+
+        assert_eq!(detector.lost_count, 2);
+        assert!((detector.loss_rate() - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn time_threshold_declares_stale_packets_lost() {
+        let mut detector = LossDetector::new();
+
+        // Establish a small RTT baseline
+        detector.on_packet_sent(0, Duration::from_millis(0), 1200);
+        detector.on_ack_received(0, Duration::from_millis(20));
+
+        // Packet 1 was sent long before packet 2, far beyond 1.125 * rtt
+        detector.on_packet_sent(1, Duration::from_millis(25), 1200);
+        detector.on_packet_sent(2, Duration::from_millis(500), 1200);
+        detector.on_ack_received(2, Duration::from_millis(520));
+
+        assert_eq!(detector.lost_count, 1);
+    }
+
+    #[test]
+    fn packet_acked_after_being_presumed_lost_counts_as_reordered() {
+        let mut detector = LossDetector::new();
+
+        for pn in 0..5 {
+            detector.on_packet_sent(pn, Duration::from_millis(pn * 10), 1200);
+        }
+        detector.on_ack_received(4, Duration::from_millis(100));
+        assert_eq!(detector.lost_count, 2);
+
+        // Packet 0 actually arrives, just late
+        detector.on_ack_received(0, Duration::from_millis(150));
+
+        assert_eq!(detector.lost_count, 1);
+        assert_eq!(detector.reordered_count, 1);
+        assert!((detector.reorder_rate() - 0.2).abs() < 1e-9);
+    }
+}
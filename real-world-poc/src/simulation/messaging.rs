@@ -0,0 +1,210 @@
+//! Discrete message-passing layer between simulation nodes, layered on top of the connection
+//! conditions [`super::network::NetworkSimulation`] already simulates: a [`Node`] behavior enqueues
+//! outgoing messages, and each tick the network schedules their delivery (or drop) against the
+//! owning connection's current `latency`/`jitter`/`packet_loss`, instead of nodes exchanging
+//! payloads instantaneously over an idealized link.
+
+use std::collections::HashMap;
+
+use rand::Rng;
+
+/// A message handed to a [`Node::step`] call, either freshly arrived in its inbox or (via
+/// [`OutgoingMessage`]) about to be scheduled for delivery
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    /// Node ID the message was sent from
+    pub from: usize,
+    /// Node ID the message is addressed to
+    pub to: usize,
+    /// Opaque application payload
+    pub payload: Vec<u8>,
+    /// Simulated tick the message was sent on, for end-to-end delivery latency
+    pub sent_at_ms: u64,
+}
+
+/// A message a [`Node::step`] wants the network to deliver on its behalf this tick
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutgoingMessage {
+    /// Node ID to deliver `payload` to
+    pub to: usize,
+    /// Opaque application payload
+    pub payload: Vec<u8>,
+}
+
+/// Pluggable per-node message-passing behavior. Each tick, a node registered via
+/// [`super::network::NetworkSimulation::set_node_behavior`] receives everything that arrived in
+/// its inbox since the last tick and returns whatever it wants to send out this tick.
+pub trait Node {
+    fn step(&mut self, inbox: Vec<Message>) -> Vec<OutgoingMessage>;
+}
+
+/// Reference [`Node`] implementation: floods any payload it hasn't seen before to every other
+/// peer in `peers`, the simplest gossip/broadcast strategy and a sanity check that the messaging
+/// layer's scheduling and delivery actually works end to end
+pub struct GossipNode {
+    id: usize,
+    peers: Vec<usize>,
+    seen: std::collections::HashSet<Vec<u8>>,
+}
+
+impl GossipNode {
+    /// A gossip node at `id` that floods newly-seen payloads to every node in `peers`
+    pub fn new(id: usize, peers: Vec<usize>) -> Self {
+        Self { id, peers, seen: std::collections::HashSet::new() }
+    }
+}
+
+impl Node for GossipNode {
+    fn step(&mut self, inbox: Vec<Message>) -> Vec<OutgoingMessage> {
+        let mut outgoing = Vec::new();
+        for message in inbox {
+            if self.seen.insert(message.payload.clone()) {
+                for &peer in &self.peers {
+                    if peer != self.id && peer != message.from {
+                        outgoing.push(OutgoingMessage { to: peer, payload: message.payload.clone() });
+                    }
+                }
+            }
+        }
+        outgoing
+    }
+}
+
+/// A message scheduled for delivery at a future simulated tick
+struct ScheduledMessage {
+    message: Message,
+    deliver_at_ms: u64,
+}
+
+/// Shared per-tick message queue [`super::network::NetworkSimulation`] drains every tick: messages
+/// enqueued via [`Self::send`] are scheduled for delivery (or dropped) against the current
+/// conditions of the connection they travel over, then handed to their destination's inbox once
+/// their scheduled tick arrives.
+#[derive(Default)]
+pub struct MessageBus {
+    /// Messages sent since the last [`Self::schedule_pending`] call, not yet scheduled
+    pending: Vec<Message>,
+    /// Messages scheduled for a future tick, awaiting [`Self::deliver_due`]
+    in_flight: Vec<ScheduledMessage>,
+    /// Arrived messages waiting for their destination's next [`Self::take_inbox`] call
+    inboxes: HashMap<usize, Vec<Message>>,
+}
+
+impl MessageBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enqueue `payload` from `from` to `to`, to be scheduled for delivery on the next
+    /// [`Self::schedule_pending`] call
+    pub fn send(&mut self, from: usize, to: usize, payload: Vec<u8>, sent_at_ms: u64) {
+        self.pending.push(Message { from, to, payload, sent_at_ms });
+    }
+
+    /// Schedule every message enqueued since the last call: dropped with probability
+    /// `packet_loss`, otherwise scheduled for delivery `latency` plus a uniform draw in
+    /// `±jitter` milliseconds after `now_ms`.
+    /// `condition_for(from, to)` resolves a `(from, to)` pair to the `(latency_ms, jitter_ms,
+    /// packet_loss)` of the connection carrying it; pairs with no connection (and therefore
+    /// `None`) are delivered immediately with no loss, since there's no link state to charge
+    /// them against. Returns how many messages were dropped this call.
+    pub fn schedule_pending(
+        &mut self,
+        rng: &mut impl Rng,
+        now_ms: u64,
+        condition_for: impl Fn(usize, usize) -> Option<(f64, f64, f64)>,
+    ) -> usize {
+        let mut dropped = 0;
+        for message in self.pending.drain(..) {
+            let (latency_ms, jitter_ms, packet_loss) =
+                condition_for(message.from, message.to).unwrap_or((0.0, 0.0, 0.0));
+
+            if rng.gen_bool(packet_loss.clamp(0.0, 1.0)) {
+                dropped += 1;
+                continue;
+            }
+
+            let jitter_draw = if jitter_ms > 0.0 { rng.gen_range(-jitter_ms..=jitter_ms) } else { 0.0 };
+            let delay_ms = (latency_ms + jitter_draw).max(0.0) as u64;
+            self.in_flight.push(ScheduledMessage { message, deliver_at_ms: now_ms + delay_ms });
+        }
+        dropped
+    }
+
+    /// Move every in-flight message whose scheduled delivery tick has arrived into its
+    /// destination's inbox, returning each delivered message's end-to-end latency in ms
+    /// (`now_ms - sent_at_ms`)
+    pub fn deliver_due(&mut self, now_ms: u64) -> Vec<f64> {
+        let mut latencies_ms = Vec::new();
+        let mut still_in_flight = Vec::with_capacity(self.in_flight.len());
+
+        for scheduled in self.in_flight.drain(..) {
+            if scheduled.deliver_at_ms <= now_ms {
+                latencies_ms.push(now_ms.saturating_sub(scheduled.message.sent_at_ms) as f64);
+                self.inboxes.entry(scheduled.message.to).or_insert_with(Vec::new).push(scheduled.message);
+            } else {
+                still_in_flight.push(scheduled);
+            }
+        }
+
+        self.in_flight = still_in_flight;
+        latencies_ms
+    }
+
+    /// Take and clear `node_id`'s inbox of everything that has arrived, for that node's next
+    /// [`Node::step`] call
+    pub fn take_inbox(&mut self, node_id: usize) -> Vec<Message> {
+        self.inboxes.remove(&node_id).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn message_is_delivered_after_its_scheduled_latency_elapses() {
+        let mut bus = MessageBus::new();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        bus.send(0, 1, b"hello".to_vec(), 0);
+        let dropped = bus.schedule_pending(&mut rng, 0, |_, _| Some((50.0, 0.0, 0.0)));
+        assert_eq!(dropped, 0);
+
+        assert!(bus.deliver_due(40).is_empty());
+        assert!(bus.take_inbox(1).is_empty());
+
+        let latencies = bus.deliver_due(50);
+        assert_eq!(latencies, vec![50.0]);
+        let inbox = bus.take_inbox(1);
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].payload, b"hello".to_vec());
+    }
+
+    #[test]
+    fn full_packet_loss_always_drops_and_never_delivers() {
+        let mut bus = MessageBus::new();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        bus.send(0, 1, b"gone".to_vec(), 0);
+        let dropped = bus.schedule_pending(&mut rng, 0, |_, _| Some((10.0, 0.0, 1.0)));
+
+        assert_eq!(dropped, 1);
+        assert!(bus.deliver_due(1000).is_empty());
+        assert!(bus.take_inbox(1).is_empty());
+    }
+
+    #[test]
+    fn gossip_node_floods_unseen_payloads_but_never_replays_them() {
+        let mut node = GossipNode::new(1, vec![0, 2, 3]);
+
+        let first_step = node.step(vec![Message { from: 0, to: 1, payload: b"news".to_vec(), sent_at_ms: 0 }]);
+        assert_eq!(first_step.len(), 2); // every peer except the sender and itself
+        assert!(first_step.iter().all(|m| m.to != 0 && m.to != 1));
+
+        let second_step = node.step(vec![Message { from: 2, to: 1, payload: b"news".to_vec(), sent_at_ms: 10 }]);
+        assert!(second_step.is_empty(), "a payload already seen shouldn't be re-flooded");
+    }
+}
@@ -3,6 +3,9 @@
 //! to create a realistic network topology.
 
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Types of network nodes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +20,32 @@ pub enum NodeType {
     ClientDevice,
 }
 
+impl NodeType {
+    /// Default link capacity for this node type, in bits per second
+    pub fn default_capacity_bps(&self) -> u32 {
+        match self {
+            NodeType::Datacenter => 4_000_000_000,
+            NodeType::EdgeServer => 1_000_000_000,
+            NodeType::ClientDevice => 100_000_000,
+            NodeType::MobileDevice => 20_000_000,
+        }
+    }
+}
+
+/// Reports how many bytes a message occupies on the wire, so
+/// [`SimulationNode::try_send`] can charge it against the node's per-step
+/// bandwidth budget. Types that don't override `payload_size` are measured
+/// by their in-memory representation.
+pub trait PayloadSize {
+    /// Size of this message in bytes
+    fn payload_size(&self) -> usize
+    where
+        Self: Sized,
+    {
+        std::mem::size_of::<Self>()
+    }
+}
+
 /// Network simulation node
 #[derive(Debug, Clone)]
 pub struct SimulationNode {
@@ -32,6 +61,11 @@ pub struct SimulationNode {
     is_mobile: bool,
     /// Location coordinates (x, y) - arbitrary units
     location: (f64, f64),
+    /// Link capacity in bits per second
+    capacity_bps: u32,
+    /// Bytes already charged against this step's bandwidth budget.
+    /// Shared via `Arc` so clones of this node observe the same load.
+    current_load: Arc<AtomicU64>,
 }
 
 impl SimulationNode {
@@ -42,13 +76,13 @@ impl SimulationNode {
             NodeType::MobileDevice => true,
             _ => false,
         };
-        
+
         // Assign random location
         let location = (
             rand::random::<f64>() * 1000.0,
             rand::random::<f64>() * 1000.0,
         );
-        
+
         Self {
             id,
             name,
@@ -56,6 +90,8 @@ impl SimulationNode {
             connected_nodes: HashSet::new(),
             is_mobile,
             location,
+            capacity_bps: node_type.default_capacity_bps(),
+            current_load: Arc::new(AtomicU64::new(0)),
         }
     }
     
@@ -93,7 +129,80 @@ impl SimulationNode {
     pub fn add_connection(&mut self, node_id: usize) {
         self.connected_nodes.insert(node_id);
     }
-    
+
+    /// Connect to another node, establishing it as a valid send target
+    pub fn connect(&mut self, node_id: usize) {
+        self.add_connection(node_id);
+    }
+
+    /// Get link capacity in bits per second
+    pub fn capacity_bps(&self) -> u32 {
+        self.capacity_bps
+    }
+
+    /// Override link capacity, in bits per second
+    pub fn set_capacity_bps(&mut self, capacity_bps: u32) {
+        self.capacity_bps = capacity_bps;
+    }
+
+    /// Convert the per-second capacity into a budget for one simulation step
+    pub fn step_budget_bytes(&self, step_time: Duration) -> u64 {
+        let step_secs = step_time.as_secs_f64();
+        ((self.capacity_bps as f64) * step_secs / 8.0) as u64
+    }
+
+    /// Bytes already charged against the current step's bandwidth budget
+    pub fn current_load(&self) -> u64 {
+        self.current_load.load(Ordering::Relaxed)
+    }
+
+    /// Remaining bandwidth budget for the current step, in bytes
+    pub fn remaining_budget(&self, step_time: Duration) -> u64 {
+        self.step_budget_bytes(step_time)
+            .saturating_sub(self.current_load())
+    }
+
+    /// Reset the per-step load counter; called once at the start of each simulation step
+    pub fn reset_step_load(&self) {
+        self.current_load.store(0, Ordering::Relaxed);
+    }
+
+    /// Try to send a message to `node_id`, charging its size against this node's
+    /// per-step bandwidth budget. Rejects the send if it would exceed the
+    /// remaining budget, or if `node_id` is not a connected peer.
+    pub fn try_send<T: PayloadSize>(
+        &self,
+        node_id: usize,
+        payload: &T,
+        step_time: Duration,
+    ) -> Result<(), String> {
+        if !self.is_connected_to(node_id) {
+            return Err(format!("node {} is not connected to node {}", self.id, node_id));
+        }
+
+        let size = payload.payload_size() as u64;
+        let budget = self.step_budget_bytes(step_time);
+
+        loop {
+            let load = self.current_load.load(Ordering::Relaxed);
+            let new_load = load + size;
+            if new_load > budget {
+                return Err(format!(
+                    "node {} bandwidth budget exceeded: {} + {} > {}",
+                    self.id, load, size, budget
+                ));
+            }
+
+            if self
+                .current_load
+                .compare_exchange(load, new_load, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Ok(());
+            }
+        }
+    }
+
     /// Check if connected to another node
     pub fn is_connected_to(&self, node_id: usize) -> bool {
         self.connected_nodes.contains(&node_id)
@@ -131,3 +240,37 @@ impl SimulationNode {
         (dx * dx + dy * dy).sqrt()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl PayloadSize for [u8; 1024] {}
+
+    #[test]
+    fn try_send_rejects_once_step_budget_is_exhausted() {
+        let mut node = SimulationNode::new(0, "node_0".to_string(), NodeType::MobileDevice);
+        node.set_capacity_bps(8_000); // 1000 bytes/sec
+        node.add_connection(1);
+
+        let step_time = Duration::from_secs(1);
+        let payload = [0u8; 1024];
+
+        assert!(node.try_send(1, &payload, step_time).is_err());
+
+        node.set_capacity_bps(1024 * 8 * 2); // budget for two payloads
+        assert!(node.try_send(1, &payload, step_time).is_ok());
+        assert!(node.try_send(1, &payload, step_time).is_ok());
+        assert!(node.try_send(1, &payload, step_time).is_err());
+
+        node.reset_step_load();
+        assert!(node.try_send(1, &payload, step_time).is_ok());
+    }
+
+    #[test]
+    fn try_send_rejects_unconnected_peers() {
+        let node = SimulationNode::new(0, "node_0".to_string(), NodeType::Datacenter);
+        let payload = [0u8; 1024];
+        assert!(node.try_send(1, &payload, Duration::from_secs(1)).is_err());
+    }
+}
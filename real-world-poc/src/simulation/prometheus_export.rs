@@ -0,0 +1,156 @@
+//! Prometheus text-exposition-format exporter for [`ConnectionMetrics`](super::network::ConnectionMetrics),
+//! served over a small blocking HTTP scrape endpoint so a long-running simulation can be graphed
+//! in Grafana instead of only inspected through the in-process `get_metrics` map. Built on
+//! `std::net::TcpListener` alone, the same way `dis_telemetry` streams PDUs over a raw
+//! `UdpSocket` rather than pulling in an HTTP framework dependency.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+
+use super::metrics::ErrorString;
+use super::network::NetworkSimulation;
+
+/// Escape a label value per the Prometheus text format: backslash and double-quote are the only
+/// characters that need it
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render `simulation`'s current per-connection metrics and protocol distribution as Prometheus
+/// text exposition format, ready to hand back as an HTTP response body
+pub fn render_metrics(simulation: &NetworkSimulation) -> String {
+    let mut out = String::new();
+
+    for (name, help, kind) in [
+        ("unrealnet_latency_ms", "Current one-way latency per connection, in milliseconds", "gauge"),
+        ("unrealnet_bandwidth_kbps", "Current bandwidth per connection, in Kbps", "gauge"),
+        ("unrealnet_packet_loss_ratio", "Current packet loss fraction per connection", "gauge"),
+        ("unrealnet_jitter_ms", "Current jitter per connection, in milliseconds", "gauge"),
+        ("unrealnet_transfer_time_ms", "Most recently simulated transfer time per connection, in milliseconds", "gauge"),
+        ("unrealnet_resilience_score", "Resilience score per connection, 0-100", "gauge"),
+        ("unrealnet_efficiency_score", "Efficiency score per connection, 0-100", "gauge"),
+        ("unrealnet_measurements_total", "Measurements recorded per connection", "counter"),
+    ] {
+        out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {kind}\n"));
+    }
+
+    let mut connections: Vec<_> = simulation.get_metrics().iter().collect();
+    connections.sort_by_key(|(&(source, dest), _)| (source, dest));
+
+    for (&(source, dest), metrics) in connections {
+        let protocol = metrics.protocol.as_ref().map(|p| p.name.as_str()).unwrap_or("none");
+        let labels = format!(
+            "source=\"{source}\",dest=\"{dest}\",protocol=\"{}\"",
+            escape_label(protocol)
+        );
+
+        out.push_str(&format!("unrealnet_latency_ms{{{labels}}} {}\n", metrics.latency.as_millis_f64()));
+        out.push_str(&format!("unrealnet_bandwidth_kbps{{{labels}}} {}\n", metrics.bandwidth.kbps()));
+        out.push_str(&format!("unrealnet_packet_loss_ratio{{{labels}}} {}\n", metrics.packet_loss.fraction()));
+        out.push_str(&format!("unrealnet_jitter_ms{{{labels}}} {}\n", metrics.jitter.as_millis_f64()));
+        out.push_str(&format!("unrealnet_transfer_time_ms{{{labels}}} {}\n", metrics.transfer_time));
+        out.push_str(&format!("unrealnet_resilience_score{{{labels}}} {}\n", metrics.resilience_score));
+        out.push_str(&format!("unrealnet_efficiency_score{{{labels}}} {}\n", metrics.efficiency_score));
+        out.push_str(&format!("unrealnet_measurements_total{{{labels}}} {}\n", metrics.timestamps.len()));
+    }
+
+    out.push_str("# HELP unrealnet_protocol_active Connections currently running each protocol\n# TYPE unrealnet_protocol_active gauge\n");
+    let mut distribution: Vec<_> = simulation.protocol_distribution().into_iter().collect();
+    distribution.sort_by(|a, b| a.0.cmp(&b.0));
+    for (protocol, count) in distribution {
+        out.push_str(&format!(
+            "unrealnet_protocol_active{{protocol=\"{}\"}} {}\n",
+            escape_label(&protocol),
+            count
+        ));
+    }
+
+    out
+}
+
+/// Minimal blocking HTTP server exposing [`render_metrics`] on every accepted connection. A
+/// scrape endpoint has exactly one resource to serve regardless of request path or method, so
+/// there's nothing to route -- the request is drained and discarded, not parsed.
+pub struct PrometheusExporter {
+    listener: TcpListener,
+}
+
+impl PrometheusExporter {
+    /// Bind the scrape endpoint to `addr` (e.g. `"127.0.0.1:9898"`, or `"127.0.0.1:0"` for an
+    /// ephemeral port discoverable via [`Self::local_addr`])
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<Self, ErrorString> {
+        Ok(Self { listener: TcpListener::bind(addr)? })
+    }
+
+    /// Local address this exporter is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr, ErrorString> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Block for the next scrape request and respond with `simulation`'s current metrics.
+    /// Callers loop this (or run it on its own thread) alongside the simulation's step loop.
+    pub fn serve_once(&self, simulation: &NetworkSimulation) -> Result<(), ErrorString> {
+        let (stream, _) = self.listener.accept()?;
+        Self::respond(stream, simulation)
+    }
+
+    fn respond(mut stream: TcpStream, simulation: &NetworkSimulation) -> Result<(), ErrorString> {
+        let mut request = [0u8; 1024];
+        let _ = stream.read(&mut request);
+
+        let body = render_metrics(simulation);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::network::ConnectionMetrics;
+    use super::super::units::Latency;
+    use std::io::BufRead;
+    use std::net::TcpStream;
+    use std::thread;
+
+    #[test]
+    fn render_metrics_emits_one_gauge_set_per_connection() {
+        let mut sim = NetworkSimulation::new();
+        let mut metrics = ConnectionMetrics::new();
+        metrics.source_id = 0;
+        metrics.dest_id = 1;
+        metrics.latency = Latency::from_millis_f64(12.5);
+        metrics.resilience_score = 87.0;
+        sim.connection_metrics.insert((0, 1), metrics);
+
+        let output = render_metrics(&sim);
+
+        assert!(output.contains("# TYPE unrealnet_latency_ms gauge"));
+        assert!(output.contains("unrealnet_latency_ms{source=\"0\",dest=\"1\",protocol=\"none\"} 12.5"));
+        assert!(output.contains("unrealnet_resilience_score{source=\"0\",dest=\"1\",protocol=\"none\"} 87"));
+    }
+
+    #[test]
+    fn scrape_over_a_real_socket_returns_the_rendered_body() {
+        let exporter = PrometheusExporter::bind("127.0.0.1:0").unwrap();
+        let addr = exporter.local_addr().unwrap();
+
+        let sim = NetworkSimulation::new();
+        let handle = thread::spawn(move || exporter.serve_once(&sim));
+
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+
+        let mut reader = std::io::BufReader::new(&client);
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line).unwrap();
+
+        handle.join().unwrap().unwrap();
+        assert!(status_line.starts_with("HTTP/1.1 200"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Geographic region assignment and inter-region latency for the large-scale simulation: instead
+//! of one flat latency profile per scenario, nodes are scattered across regions and a connection
+//! crossing regions picks up that pair's base inter-region delay on top of the scenario's own
+//! `base_latency`, so international/satellite-style scenarios emerge from topology rather than a
+//! single knob.
+
+use std::collections::HashMap;
+
+use rand::Rng;
+
+/// Geographic region a node is assigned to during [`super::network::NetworkSimulation::initialize`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    Europe,
+    NorthAmerica,
+    Asia,
+}
+
+impl Region {
+    /// All regions, in the fixed order [`RegionsData::new`] assigns weights and builds its matrix
+    pub const ALL: [Region; 3] = [Region::Europe, Region::NorthAmerica, Region::Asia];
+
+    /// Short label used in metrics/visualizer output
+    pub fn label(&self) -> &'static str {
+        match self {
+            Region::Europe => "europe",
+            Region::NorthAmerica => "north_america",
+            Region::Asia => "asia",
+        }
+    }
+}
+
+fn region_pair_key(a: Region, b: Region) -> (Region, Region) {
+    if (a as u8) <= (b as u8) { (a, b) } else { (b, a) }
+}
+
+/// Region list, per-node assignment weights, and the symmetric inter-region base latency matrix
+pub struct RegionsData {
+    /// Probability weight for drawing each region in [`Self::assign_region`]; not required to
+    /// sum to 1.0, just relative
+    weights: HashMap<Region, f64>,
+    /// Symmetric inter-region one-way base latency, in ms, keyed by [`region_pair_key`]
+    matrix: HashMap<(Region, Region), f64>,
+}
+
+impl RegionsData {
+    /// Build the default region set: every region equally likely, and a base latency matrix
+    /// reflecting real-world intercontinental fiber/satellite delay (same-region is cheap,
+    /// transatlantic/transpacific links are not)
+    pub fn new() -> Self {
+        let mut weights = HashMap::new();
+        for region in Region::ALL {
+            weights.insert(region, 1.0);
+        }
+
+        let mut matrix = HashMap::new();
+        matrix.insert(region_pair_key(Region::Europe, Region::Europe), 5.0);
+        matrix.insert(region_pair_key(Region::NorthAmerica, Region::NorthAmerica), 5.0);
+        matrix.insert(region_pair_key(Region::Asia, Region::Asia), 5.0);
+        matrix.insert(region_pair_key(Region::Europe, Region::NorthAmerica), 80.0);
+        matrix.insert(region_pair_key(Region::Europe, Region::Asia), 120.0);
+        matrix.insert(region_pair_key(Region::NorthAmerica, Region::Asia), 150.0);
+
+        Self { weights, matrix }
+    }
+
+    /// Draw a region for a new node, weighted by [`Self::weights`]
+    pub fn assign_region(&self, rng: &mut impl Rng) -> Region {
+        let total: f64 = self.weights.values().sum();
+        let mut draw = rng.gen_range(0.0..total.max(f64::MIN_POSITIVE));
+
+        for region in Region::ALL {
+            let weight = *self.weights.get(&region).unwrap_or(&0.0);
+            if draw < weight {
+                return region;
+            }
+            draw -= weight;
+        }
+
+        // Falls through only on floating-point edge cases at the top of the range
+        Region::ALL[Region::ALL.len() - 1]
+    }
+
+    /// Base one-way latency, in ms, between `a` and `b`; `0.0` if the pair isn't in the matrix
+    /// (every [`Region::ALL`] pair is seeded by [`Self::new`], so this only matters for a custom
+    /// matrix missing an entry)
+    pub fn inter_region_latency_ms(&self, a: Region, b: Region) -> f64 {
+        self.matrix.get(&region_pair_key(a, b)).copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn same_region_latency_is_lower_than_cross_region() {
+        let regions = RegionsData::new();
+        let same = regions.inter_region_latency_ms(Region::Europe, Region::Europe);
+        let cross = regions.inter_region_latency_ms(Region::Europe, Region::Asia);
+        assert!(same < cross);
+    }
+
+    #[test]
+    fn inter_region_latency_is_symmetric() {
+        let regions = RegionsData::new();
+        assert_eq!(
+            regions.inter_region_latency_ms(Region::NorthAmerica, Region::Asia),
+            regions.inter_region_latency_ms(Region::Asia, Region::NorthAmerica),
+        );
+    }
+
+    #[test]
+    fn assign_region_only_returns_known_regions() {
+        let regions = RegionsData::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..50 {
+            let region = regions.assign_region(&mut rng);
+            assert!(Region::ALL.contains(&region));
+        }
+    }
+}
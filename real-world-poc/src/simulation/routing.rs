@@ -0,0 +1,277 @@
+//! Multi-hop message routing over the simulation's sparse connection graph. A [`Topology`] path
+//! only decides the aggregated conditions a *single* [`NodeConnection`] is built with in
+//! `create_connections`; at low connection density a source/destination pair that never drew a
+//! direct connection of their own has no way to exchange traffic at all. `route`/`send_message`
+//! treat the flat `NodeConnection` list as its own graph and hop across however many of them a
+//! path needs, so a message can still reach a destination through intermediate connections.
+//!
+//! [`Topology`]: super::topology::Topology
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use super::network::NodeConnection;
+
+/// Policy used to choose a path across the connection graph in [`route`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageRoutingPolicy {
+    /// Minimum end-to-end latency (Dijkstra over per-connection latency)
+    ShortestLatency,
+    /// Widest-bandwidth path: maximize the bottleneck (minimum) hop bandwidth along the path,
+    /// rather than the fewest hops or lowest latency
+    WidestBandwidth,
+    /// Fewest-hop path, ignoring every link's conditions entirely -- models a flood-fill
+    /// broadcast that always finds *a* path if one exists rather than the best one
+    Flooding,
+}
+
+/// End-to-end outcome of one [`send_message`] call
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMetrics {
+    /// Node ids visited, `source..=dest` inclusive
+    pub path: Vec<usize>,
+    /// Sum of each traversed connection's latency and jitter
+    pub end_to_end_latency_ms: f64,
+    /// Product of each traversed connection's `(1.0 - packet_loss)`, i.e. the probability the
+    /// message survives every hop
+    pub delivery_ratio: f64,
+    /// Number of connections traversed
+    pub hop_count: usize,
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+fn other_end(conn: &NodeConnection, node: usize) -> usize {
+    if conn.source_id == node { conn.dest_id } else { conn.source_id }
+}
+
+/// Connection indices touching each node, since a node can appear as either endpoint
+fn build_adjacency(connections: &[NodeConnection]) -> HashMap<usize, Vec<usize>> {
+    let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (idx, conn) in connections.iter().enumerate() {
+        adjacency.entry(conn.source_id).or_default().push(idx);
+        adjacency.entry(conn.dest_id).or_default().push(idx);
+    }
+    adjacency
+}
+
+/// Compute a path from `source` to `dest` across `connections` per `policy`, or `None` if
+/// unreachable
+pub fn route(
+    connections: &[NodeConnection],
+    policy: MessageRoutingPolicy,
+    source: usize,
+    dest: usize,
+) -> Option<Vec<usize>> {
+    if source == dest {
+        return Some(vec![source]);
+    }
+
+    let adjacency = build_adjacency(connections);
+
+    match policy {
+        MessageRoutingPolicy::ShortestLatency => shortest_latency_path(connections, &adjacency, source, dest),
+        MessageRoutingPolicy::WidestBandwidth => widest_bandwidth_path(connections, &adjacency, source, dest),
+        MessageRoutingPolicy::Flooding => fewest_hop_path(&adjacency, connections, source, dest),
+    }
+}
+
+/// Dijkstra shortest path weighted by per-connection latency
+fn shortest_latency_path(
+    connections: &[NodeConnection],
+    adjacency: &HashMap<usize, Vec<usize>>,
+    source: usize,
+    dest: usize,
+) -> Option<Vec<usize>> {
+    #[derive(PartialEq)]
+    struct Candidate {
+        cost: f64,
+        node: usize,
+    }
+    impl Eq for Candidate {}
+    impl Ord for Candidate {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+        }
+    }
+    impl PartialOrd for Candidate {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    let mut dist: HashMap<usize, f64> = HashMap::new();
+    let mut prev: HashMap<usize, usize> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    dist.insert(source, 0.0);
+    heap.push(Candidate { cost: 0.0, node: source });
+
+    while let Some(Candidate { cost, node }) = heap.pop() {
+        if node == dest {
+            break;
+        }
+        if cost > *dist.get(&node).unwrap_or(&f64::INFINITY) {
+            continue;
+        }
+
+        for &edge_idx in adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[]) {
+            let conn = &connections[edge_idx];
+            let neighbor = other_end(conn, node);
+            let next_cost = cost + conn.latency.as_millis_f64();
+            if next_cost < *dist.get(&neighbor).unwrap_or(&f64::INFINITY) {
+                dist.insert(neighbor, next_cost);
+                prev.insert(neighbor, node);
+                heap.push(Candidate { cost: next_cost, node: neighbor });
+            }
+        }
+    }
+
+    reconstruct_path(&prev, source, dest)
+}
+
+/// Widest path: at each step, extend the candidate whose bottleneck (minimum hop bandwidth so
+/// far) is largest, rather than the one with the lowest accumulated cost
+fn widest_bandwidth_path(
+    connections: &[NodeConnection],
+    adjacency: &HashMap<usize, Vec<usize>>,
+    source: usize,
+    dest: usize,
+) -> Option<Vec<usize>> {
+    #[derive(PartialEq)]
+    struct Candidate {
+        bottleneck_kbps: f64,
+        node: usize,
+    }
+    impl Eq for Candidate {}
+    impl Ord for Candidate {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.bottleneck_kbps.partial_cmp(&other.bottleneck_kbps).unwrap_or(Ordering::Equal)
+        }
+    }
+    impl PartialOrd for Candidate {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    let mut best: HashMap<usize, f64> = HashMap::new();
+    let mut prev: HashMap<usize, usize> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    best.insert(source, f64::INFINITY);
+    heap.push(Candidate { bottleneck_kbps: f64::INFINITY, node: source });
+
+    while let Some(Candidate { bottleneck_kbps, node }) = heap.pop() {
+        if node == dest {
+            break;
+        }
+        if bottleneck_kbps < *best.get(&node).unwrap_or(&f64::NEG_INFINITY) {
+            continue;
+        }
+
+        for &edge_idx in adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[]) {
+            let conn = &connections[edge_idx];
+            let neighbor = other_end(conn, node);
+            let candidate_bottleneck = bottleneck_kbps.min(conn.bandwidth.kbps());
+            if candidate_bottleneck > *best.get(&neighbor).unwrap_or(&f64::NEG_INFINITY) {
+                best.insert(neighbor, candidate_bottleneck);
+                prev.insert(neighbor, node);
+                heap.push(Candidate { bottleneck_kbps: candidate_bottleneck, node: neighbor });
+            }
+        }
+    }
+
+    reconstruct_path(&prev, source, dest)
+}
+
+/// Breadth-first search over connection count alone, ignoring every hop's conditions
+fn fewest_hop_path(
+    adjacency: &HashMap<usize, Vec<usize>>,
+    connections: &[NodeConnection],
+    source: usize,
+    dest: usize,
+) -> Option<Vec<usize>> {
+    let mut prev: HashMap<usize, usize> = HashMap::new();
+    let mut visited = std::collections::HashSet::new();
+    let mut queue = std::collections::VecDeque::new();
+
+    visited.insert(source);
+    queue.push_back(source);
+
+    while let Some(node) = queue.pop_front() {
+        if node == dest {
+            break;
+        }
+
+        for &edge_idx in adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[]) {
+            let neighbor = other_end(&connections[edge_idx], node);
+            if visited.insert(neighbor) {
+                prev.insert(neighbor, node);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    reconstruct_path(&prev, source, dest)
+}
+
+fn reconstruct_path(prev: &HashMap<usize, usize>, source: usize, dest: usize) -> Option<Vec<usize>> {
+    if source == dest {
+        return Some(vec![source]);
+    }
+    if !prev.contains_key(&dest) {
+        return None;
+    }
+
+    let mut path = vec![dest];
+    let mut current = dest;
+    while current != source {
+        current = *prev.get(&current)?;
+        path.push(current);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Walk `route`'s path hop by hop, summing each traversed connection's latency and jitter and
+/// compounding its packet loss into a delivery-probability product, and debiting `size_bytes`
+/// onto every traversed connection's `queued_bytes` so the next `apply_capacity_contention` tick
+/// feels this send alongside the connection's regular traffic.
+pub fn send_message(
+    connections: &mut [NodeConnection],
+    policy: MessageRoutingPolicy,
+    source: usize,
+    dest: usize,
+    size_bytes: u64,
+) -> Option<PathMetrics> {
+    let path = route(connections, policy, source, dest)?;
+    if path.len() < 2 {
+        return Some(PathMetrics { path, end_to_end_latency_ms: 0.0, delivery_ratio: 1.0, hop_count: 0 });
+    }
+
+    let mut index_by_pair: HashMap<(usize, usize), usize> = HashMap::new();
+    for (idx, conn) in connections.iter().enumerate() {
+        index_by_pair.insert(edge_key(conn.source_id, conn.dest_id), idx);
+    }
+
+    let mut end_to_end_latency_ms = 0.0;
+    let mut delivery_ratio = 1.0;
+
+    for window in path.windows(2) {
+        let idx = *index_by_pair.get(&edge_key(window[0], window[1]))?;
+        let conn = &mut connections[idx];
+        end_to_end_latency_ms += conn.latency.as_millis_f64() + conn.jitter.as_millis_f64();
+        delivery_ratio *= 1.0 - conn.packet_loss.fraction();
+        conn.queued_bytes += size_bytes;
+    }
+
+    Some(PathMetrics {
+        hop_count: path.len() - 1,
+        end_to_end_latency_ms,
+        delivery_ratio,
+        path,
+    })
+}
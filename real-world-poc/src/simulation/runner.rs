@@ -0,0 +1,233 @@
+//! Pluggable execution strategies for advancing a [`NetworkSimulation`], selected via
+//! [`super::SimulationConfig::runner`] and built once in [`super::LargeScaleSimulator::initialize`].
+//! `LargeScaleSimulator::run` used to call `network.run(duration)` directly for every scenario;
+//! it now drives whichever [`Runner`] the config picked instead, so the same scenario sweep can be
+//! replayed under a different execution model without touching the sweep itself.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::network::{NetworkSimulation, TICK_MS};
+
+/// Advances a [`NetworkSimulation`] by a span of simulated time, dispatching its own ticks and
+/// message delivery however this strategy sees fit
+pub trait Runner: std::fmt::Debug {
+    fn advance(&mut self, network: &mut NetworkSimulation, duration: Duration) -> Result<(), String>;
+}
+
+/// Current fixed-step behavior: every call just hands `duration` straight to
+/// [`NetworkSimulation::run`], which ticks every connection in lock-step wavefronts.
+#[derive(Debug, Default)]
+pub struct SyncRunner;
+
+impl Runner for SyncRunner {
+    fn advance(&mut self, network: &mut NetworkSimulation, duration: Duration) -> Result<(), String> {
+        network.run(duration)
+    }
+}
+
+/// Drives the network off a per-node priority queue ordered by scheduled delivery time instead of
+/// every node progressing at the same rate: each node's next event fires after its fastest
+/// current connection's latency (rounded to the nearest tick, [`NetworkSimulation::run_steps`]'s
+/// own granularity), so nodes on quick links get re-checked every tick while high-latency nodes
+/// fall behind and are caught up in bigger jumps, earliest-event-first. The queue persists across
+/// calls so event cadence survives from one scenario to the next instead of resetting every
+/// `advance`; `clock_tick` is this runner's own notion of elapsed ticks, advanced only through
+/// [`NetworkSimulation::run_steps_for_nodes`] and [`NetworkSimulation::run_steps`] so it always
+/// matches the network's actual `current_time`.
+#[derive(Debug, Default)]
+pub struct AsyncRunner {
+    queue: BinaryHeap<Reverse<(u64, usize)>>,
+    clock_tick: u64,
+}
+
+impl AsyncRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Earliest-event-first delay, in ticks, for `node_id`'s next wakeup: its fastest attached
+    /// connection's latency rounded to the nearest tick, or one tick if it has no connections yet
+    fn next_delay_ticks(network: &NetworkSimulation, node_id: usize) -> u64 {
+        network
+            .get_connections()
+            .iter()
+            .filter(|conn| conn.source_id == node_id || conn.dest_id == node_id)
+            .map(|conn| ((conn.latency.as_millis_f64() / TICK_MS as f64).round() as u64).max(1))
+            .min()
+            .unwrap_or(1)
+    }
+}
+
+impl Runner for AsyncRunner {
+    fn advance(&mut self, network: &mut NetworkSimulation, duration: Duration) -> Result<(), String> {
+        let duration_ticks = ((duration.as_millis() as u64) / TICK_MS).max(1);
+        let deadline_tick = self.clock_tick + duration_ticks;
+
+        if self.queue.is_empty() {
+            for &node_id in network.get_nodes().keys() {
+                self.queue.push(Reverse((self.clock_tick + 1, node_id)));
+            }
+        }
+
+        while let Some(&Reverse((event_tick, _))) = self.queue.peek() {
+            if event_tick > deadline_tick {
+                break;
+            }
+
+            // Pop every node due at this exact tick together, not just the earliest-sorted one --
+            // two or more nodes commonly tie on the same event_tick (e.g. every node's very first
+            // event), and batching them into one `run_steps_for_nodes` call keeps that tick's
+            // bookkeeping/metrics running exactly once, with every due node's connections
+            // refreshed before metrics are sampled, rather than popping them one at a time and
+            // having a later tied node's refresh land after that tick's metrics already captured
+            // an earlier tied node's (or nobody's) conditions.
+            let mut due_nodes = Vec::new();
+            while let Some(&Reverse((tick, node_id))) = self.queue.peek() {
+                if tick != event_tick {
+                    break;
+                }
+                self.queue.pop();
+                due_nodes.push(node_id);
+            }
+
+            // Every tick between the last batch and this one still advances the network's own
+            // clock/messaging/metrics bookkeeping, but only this batch's own connections actually
+            // get their conditions refreshed here -- a node whose next event is still far off
+            // keeps whatever conditions its connections had as of its own last event, instead of
+            // every popped event re-ticking the whole network regardless of whose turn it was.
+            let elapsed_ticks = event_tick.saturating_sub(self.clock_tick);
+            if elapsed_ticks > 0 {
+                network.run_steps_for_nodes(&due_nodes, elapsed_ticks as usize);
+                self.clock_tick = event_tick;
+            }
+
+            for &node_id in &due_nodes {
+                let next_delay = Self::next_delay_ticks(network, node_id);
+                self.queue.push(Reverse((event_tick + next_delay, node_id)));
+            }
+        }
+
+        // The queue may run dry of due events before `deadline_tick` (every remaining node's next
+        // event lands past it); still advance the network the rest of the way so `advance` always
+        // covers the full `duration` asked for, the same contract every other `Runner` honors.
+        // There's no more-due node left to attribute this tail span to, so it folds into the
+        // network's ordinary whole-network tick rather than any one node's differential cadence.
+        if self.clock_tick < deadline_tick {
+            network.run_steps((deadline_tick - self.clock_tick) as usize);
+            self.clock_tick = deadline_tick;
+        }
+
+        Ok(())
+    }
+}
+
+/// Partitions connections by [`Region`](super::regions::Region) instead of by equal-size shard: every tick, a connection
+/// whose two endpoints share a region is ticked on that region's own thread, isolated from every
+/// other region's connections for the duration of the tick, while anything crossing regions (or
+/// missing one) falls into a shared boundary layer. All layers still join and synchronize before
+/// the tick's traffic claims land on the shared topology, so this doesn't change *what* a tick
+/// computes -- only that same-region work can genuinely run independently of other regions'
+/// instead of being interleaved into arbitrary fixed-size shards. See
+/// [`NetworkSimulation::run_steps_layered`] for the per-tick partitioning itself.
+#[derive(Debug, Default)]
+pub struct LayeredRunner;
+
+impl LayeredRunner {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Runner for LayeredRunner {
+    fn advance(&mut self, network: &mut NetworkSimulation, duration: Duration) -> Result<(), String> {
+        let ticks = ((duration.as_millis() as u64) / TICK_MS).max(1);
+        network.run_steps_layered(ticks as usize);
+        Ok(())
+    }
+}
+
+/// Which [`Runner`] [`super::LargeScaleSimulator::initialize`] should build, picked via
+/// [`super::SimulationConfig::runner`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunnerStrategy {
+    /// [`SyncRunner`]: fixed-step wavefronts, the long-standing default
+    Sync,
+    /// [`AsyncRunner`]: priority-queue-driven, earliest-event-first
+    Async,
+    /// [`LayeredRunner`]: per-region connection layers ticked in isolation, synchronized every tick
+    Layered,
+}
+
+impl RunnerStrategy {
+    pub fn build(self) -> Box<dyn Runner> {
+        match self {
+            RunnerStrategy::Sync => Box::new(SyncRunner),
+            RunnerStrategy::Async => Box::new(AsyncRunner::new()),
+            RunnerStrategy::Layered => Box::new(LayeredRunner::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::scenarios::ScenarioManager;
+    use super::super::topology::{RoutingPolicy, TopologyKind};
+
+    fn network() -> NetworkSimulation {
+        let mut network = NetworkSimulation::with_seed(42);
+        network.initialize(6, 0.8, TopologyKind::Mesh, RoutingPolicy::ShortestPath, 42).unwrap();
+
+        let mut scenarios = ScenarioManager::new();
+        scenarios.load_predefined_scenarios();
+        network.apply_scenario(&scenarios.get_scenario("ideal").unwrap());
+
+        network
+    }
+
+    #[test]
+    fn sync_runner_advances_by_the_full_duration() {
+        let mut network = network();
+        let before = network.current_time;
+        SyncRunner.advance(&mut network, Duration::from_millis(500)).unwrap();
+        assert_eq!(network.current_time - before, 500);
+    }
+
+    #[test]
+    fn async_runner_advances_by_exactly_the_requested_duration() {
+        let mut network = network();
+        let before = network.current_time;
+        let mut runner = AsyncRunner::new();
+        runner.advance(&mut network, Duration::from_millis(1000)).unwrap();
+        assert_eq!(network.current_time - before, 1000);
+
+        // A second call on the same runner should pick up where the queue left off rather than
+        // re-seeding every node, and still cover exactly its own requested span
+        runner.advance(&mut network, Duration::from_millis(500)).unwrap();
+        assert_eq!(network.current_time - before, 1500);
+    }
+
+    #[test]
+    fn layered_runner_advances_by_exactly_the_requested_duration() {
+        let mut network = network();
+        let before = network.current_time;
+        let mut runner = LayeredRunner::new();
+        runner.advance(&mut network, Duration::from_millis(900)).unwrap();
+        assert_eq!(network.current_time - before, 900);
+    }
+
+    #[test]
+    fn layered_runner_keeps_every_connection_across_the_split() {
+        // Region-partitioned ticking re-sorts `network.connections` and splits it across threads
+        // via `split_at_mut`; this only checks that no connection is lost or duplicated along the
+        // way, by checking the count survives a run untouched. It does not count ticks.
+        let mut network = network();
+        let before = network.connection_count();
+        LayeredRunner::new().advance(&mut network, Duration::from_millis(300)).unwrap();
+        assert_eq!(network.connection_count(), before);
+    }
+}
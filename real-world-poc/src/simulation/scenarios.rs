@@ -2,8 +2,15 @@
 //! Provides predefined network scenarios with different conditions to showcase
 //! the adaptive capabilities of the Dynamic Protocols Infra Physics Generator.
 
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::metrics::ErrorString;
+
 /// Network scenario with specific conditions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkScenario {
     /// Scenario name
     pub name: String,
@@ -25,6 +32,17 @@ pub struct NetworkScenario {
     pub packet_loss_variation: f64,
     /// Jitter variation range
     pub jitter_variation: f64,
+    /// Token-bucket burst allowance above `base_bandwidth`, in kbit; `None` (the default, and what
+    /// every predefined scenario leaves it at) means no burst headroom beyond the steady rate,
+    /// matching behavior from before this field existed. Set via [`Self::with_burst_kbit`].
+    #[serde(default)]
+    pub burst_kbit: Option<f64>,
+    /// How many bytes a connection's backlog may carry before the overflow is tail-dropped and
+    /// counted as loss; `None` (the default) falls back to the node-budget-derived limit in
+    /// [`super::network::NodeConnection::apply_capacity_contention`]. Set via
+    /// [`Self::with_buffer_limit_bytes`].
+    #[serde(default)]
+    pub buffer_limit_bytes: Option<u64>,
 }
 
 impl NetworkScenario {
@@ -52,8 +70,25 @@ impl NetworkScenario {
             bandwidth_variation,
             packet_loss_variation,
             jitter_variation,
+            burst_kbit: None,
+            buffer_limit_bytes: None,
         }
     }
+
+    /// Give this scenario's connections a token-bucket burst allowance above `base_bandwidth`,
+    /// in kbit, so traffic that's been idle can briefly send faster than the steady rate rather
+    /// than being paced as if every tick were fully loaded
+    pub fn with_burst_kbit(mut self, burst_kbit: f64) -> Self {
+        self.burst_kbit = Some(burst_kbit);
+        self
+    }
+
+    /// Cap how many bytes this scenario's connections may backlog before the overflow is
+    /// tail-dropped as loss, overriding the node-budget-derived default
+    pub fn with_buffer_limit_bytes(mut self, buffer_limit_bytes: u64) -> Self {
+        self.buffer_limit_bytes = Some(buffer_limit_bytes);
+        self
+    }
 }
 
 /// Manager for network scenarios
@@ -117,7 +152,8 @@ impl ScenarioManager {
             5.0,
         ));
         
-        // Add wireless interference scenario
+        // Add wireless interference scenario. A consumer AP's egress queue is small, so a
+        // backlog tail-drops far sooner than the node-budget-derived default would let it.
         self.add_scenario(NetworkScenario::new(
             "wireless_interference",
             "Wireless networks with interference causing packet loss and jitter",
@@ -129,7 +165,7 @@ impl ScenarioManager {
             1500.0,
             0.1,
             15.0,
-        ));
+        ).with_buffer_limit_bytes(65_536));
         
         // Add mobile handover scenario
         self.add_scenario(NetworkScenario::new(
@@ -159,7 +195,8 @@ impl ScenarioManager {
             3.0,
         ));
         
-        // Add satellite connection scenario
+        // Add satellite connection scenario. The long fat pipe means a flow that's been quiet
+        // for a round-trip can legitimately burst well above its steady rate once it resumes.
         self.add_scenario(NetworkScenario::new(
             "satellite",
             "Satellite connections with very high latency but decent bandwidth",
@@ -171,7 +208,7 @@ impl ScenarioManager {
             1000.0,
             0.03,
             8.0,
-        ));
+        ).with_burst_kbit(2000.0));
         
         // Add extreme conditions scenario
         self.add_scenario(NetworkScenario::new(
@@ -190,6 +227,35 @@ impl ScenarioManager {
         println!("Loaded {} predefined network scenarios", self.scenarios.len());
     }
     
+    /// Replace the scenario list with an explicit set, e.g. loaded from an
+    /// [`super::experiment::ExperimentConfig`] instead of the compiled-in predefined list
+    pub fn load_scenarios(&mut self, scenarios: Vec<NetworkScenario>) {
+        self.scenarios.clear();
+        for scenario in scenarios {
+            self.add_scenario(scenario);
+        }
+    }
+
+    /// Replace the scenario list with one loaded from a standalone JSON file holding a
+    /// `Vec<NetworkScenario>`, so a scenario set can be authored and shared without going through
+    /// a full [`super::experiment::ExperimentConfig`]
+    pub fn load_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ErrorString> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| ErrorString(format!("Failed to read scenario file: {}", e)))?;
+        let scenarios: Vec<NetworkScenario> = serde_json::from_str(&contents)
+            .map_err(|e| ErrorString(format!("Failed to parse scenario file: {}", e)))?;
+        self.load_scenarios(scenarios);
+        Ok(())
+    }
+
+    /// Write the current scenario list to `path` as JSON, the inverse of [`Self::load_from_file`]
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ErrorString> {
+        let json = serde_json::to_string_pretty(&self.get_all_scenarios())
+            .map_err(|e| ErrorString(format!("Failed to serialize scenarios: {}", e)))?;
+        fs::write(path, json)
+            .map_err(|e| ErrorString(format!("Failed to write scenario file: {}", e)))
+    }
+
     /// Add a scenario
     pub fn add_scenario(&mut self, scenario: NetworkScenario) {
         self.scenarios.insert(scenario.name.clone(), scenario);
@@ -210,3 +276,28 @@ impl ScenarioManager {
         self.scenarios.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scenarios_round_trip_through_a_json_file() {
+        let mut manager = ScenarioManager::new();
+        manager.add_scenario(NetworkScenario::new(
+            "custom", "a hand-authored scenario", 10.0, 2000.0, 0.01, 2.0, 1.0, 100.0, 0.001, 0.5,
+        ));
+
+        let path = std::env::temp_dir().join(format!("scenarios_round_trip_{}.json", std::process::id()));
+        manager.save_to_file(&path).unwrap();
+
+        let mut loaded = ScenarioManager::new();
+        loaded.load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.scenario_count(), 1);
+        let scenario = loaded.get_scenario("custom").unwrap();
+        assert_eq!(scenario.base_latency, 10.0);
+        assert_eq!(scenario.description, "a hand-authored scenario");
+    }
+}
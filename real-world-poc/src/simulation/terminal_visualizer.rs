@@ -0,0 +1,179 @@
+//! Live terminal dashboard for the simulation
+//! Renders rolling braille-marker sparklines and per-scenario gauges in place,
+//! the way `bottom` redraws its network graph every tick, so long runs give
+//! immediate feedback instead of waiting for the final HTML report.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::network::NetworkSimulation;
+use super::metrics::MetricsCollector;
+
+/// Maximum number of samples kept per rolling curve
+const SPARKLINE_HISTORY: usize = 80;
+
+/// Braille dot patterns used to build one-row sparklines, low to high
+const BRAILLE_LEVELS: [char; 5] = ['⣀', '⣤', '⣶', '⣿', '⡇'];
+
+/// Rolling curves tracked for the scenario currently being drawn
+#[derive(Debug, Default)]
+struct ScenarioCurves {
+    latency: Vec<f64>,
+    bandwidth: Vec<f64>,
+    packet_loss: Vec<f64>,
+}
+
+impl ScenarioCurves {
+    fn push(&mut self, latency: f64, bandwidth: f64, packet_loss: f64) {
+        Self::push_bounded(&mut self.latency, latency);
+        Self::push_bounded(&mut self.bandwidth, bandwidth);
+        Self::push_bounded(&mut self.packet_loss, packet_loss);
+    }
+
+    fn push_bounded(series: &mut Vec<f64>, value: f64) {
+        series.push(value);
+        if series.len() > SPARKLINE_HISTORY {
+            series.remove(0);
+        }
+    }
+}
+
+/// Live terminal dashboard, used alongside [`super::PerformanceVisualizer`] when
+/// `live_enabled` is set, redrawing in place after every `update()` call.
+pub struct TerminalVisualizer {
+    /// Whether the dashboard is currently drawing
+    enabled: bool,
+    /// Rolling curves keyed by scenario name
+    curves: HashMap<String, ScenarioCurves>,
+    /// Last known overall improvement per scenario, for the summary table
+    overall_improvement: HashMap<String, f64>,
+    /// Number of redraws performed, used to size the scroll-back clear
+    frame_count: usize,
+}
+
+impl TerminalVisualizer {
+    /// Create a new terminal visualizer, initially disabled
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            curves: HashMap::new(),
+            overall_improvement: HashMap::new(),
+            frame_count: 0,
+        }
+    }
+
+    /// Enable or disable live terminal drawing
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if enabled {
+            self.curves.clear();
+            self.overall_improvement.clear();
+            self.frame_count = 0;
+        }
+    }
+
+    /// Record the current simulation/metrics state and redraw the dashboard in place
+    pub fn update(&mut self, simulation: &NetworkSimulation, metrics: &MetricsCollector) {
+        if !self.enabled {
+            return;
+        }
+
+        let scenario_name = simulation.get_current_scenario()
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let connection_metrics = simulation.get_metrics();
+        let (mut latency, mut bandwidth, mut packet_loss, mut count) = (0.0, 0.0, 0.0, 0usize);
+        for (_, conn) in connection_metrics {
+            let (avg_latency, avg_bandwidth, avg_packet_loss, _, _) = conn.averages();
+            latency += avg_latency;
+            bandwidth += avg_bandwidth;
+            packet_loss += avg_packet_loss;
+            count += 1;
+        }
+        if count > 0 {
+            latency /= count as f64;
+            bandwidth /= count as f64;
+            packet_loss /= count as f64;
+        }
+
+        self.curves.entry(scenario_name.clone())
+            .or_insert_with(ScenarioCurves::default)
+            .push(latency, bandwidth, packet_loss);
+
+        let improvement = metrics.calculate_scenario_improvement(&scenario_name);
+        self.overall_improvement.insert(scenario_name.clone(), improvement.overall);
+
+        self.frame_count += 1;
+        self.render(&scenario_name);
+    }
+
+    /// Redraw the dashboard for the given scenario in place using ANSI cursor control
+    fn render(&self, active_scenario: &str) {
+        let mut out = io::stdout();
+
+        // Clear the region we drew last frame: 3 header lines + 3 sparklines (2 rows
+        // each) + a blank line + the table heading + one table row per known scenario.
+        let lines_to_clear = 3 + 6 + 1 + self.overall_improvement.len() + 1;
+        if self.frame_count > 1 {
+            let _ = write!(out, "\x1b[{}A", lines_to_clear);
+        }
+
+        let _ = writeln!(out, "\x1b[2K=== Live Simulation Dashboard ===");
+        let _ = writeln!(out, "\x1b[2KScenario: {}", active_scenario);
+        let _ = writeln!(out, "\x1b[2K");
+
+        if let Some(curves) = self.curves.get(active_scenario) {
+            self.render_sparkline(&mut out, "Latency (ms)", &curves.latency);
+            self.render_sparkline(&mut out, "Bandwidth (Kbps)", &curves.bandwidth);
+            self.render_sparkline(&mut out, "Packet Loss (%)", &curves.packet_loss);
+        } else {
+            let _ = writeln!(out, "\x1b[2K(no samples yet)");
+            let _ = writeln!(out, "\x1b[2K");
+            let _ = writeln!(out, "\x1b[2K");
+            let _ = writeln!(out, "\x1b[2K");
+            let _ = writeln!(out, "\x1b[2K");
+            let _ = writeln!(out, "\x1b[2K");
+        }
+
+        let _ = writeln!(out, "\x1b[2K");
+        let _ = writeln!(out, "\x1b[2KOverall improvement per scenario:");
+        let mut scenarios: Vec<&String> = self.overall_improvement.keys().collect();
+        scenarios.sort();
+        for name in scenarios {
+            let value = self.overall_improvement[name];
+            let gauge = Self::gauge_bar(value);
+            let _ = writeln!(out, "\x1b[2K  {:<22} {} {:>6.2}%", name, gauge, value);
+        }
+
+        let _ = out.flush();
+    }
+
+    /// Render one rolling curve as its label row followed by a one-row braille sparkline
+    fn render_sparkline(&self, out: &mut impl Write, label: &str, series: &[f64]) {
+        let _ = writeln!(out, "\x1b[2K{}", label);
+        if series.is_empty() {
+            let _ = writeln!(out, "\x1b[2K(no samples yet)");
+            return;
+        }
+
+        let min = series.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = series.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let range = (max - min).max(1e-9);
+
+        let line: String = series.iter().map(|&v| {
+            let normalized = ((v - min) / range).clamp(0.0, 1.0);
+            let level = (normalized * (BRAILLE_LEVELS.len() - 1) as f64).round() as usize;
+            BRAILLE_LEVELS[level]
+        }).collect();
+
+        let _ = writeln!(out, "\x1b[2K{} (min {:.2}, max {:.2})", line, min, max);
+    }
+
+    /// Render a compact ASCII gauge bar for a -100..100 improvement percentage
+    fn gauge_bar(value: f64) -> String {
+        let clamped = value.clamp(-100.0, 100.0);
+        let filled = ((clamped + 100.0) / 200.0 * 20.0).round() as usize;
+        format!("[{}{}]", "#".repeat(filled), "-".repeat(20usize.saturating_sub(filled)))
+    }
+}
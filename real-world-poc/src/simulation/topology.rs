@@ -0,0 +1,481 @@
+//! Structured interconnect topology and routing for the large-scale simulation: instead of a
+//! flat randomly-wired graph, connections can be routed over a mesh/torus/dragonfly network so
+//! the `NetworkCondition`s the protocol engine sees are the end-to-end aggregation of per-hop
+//! link conditions along a computed path, rather than a single synthetic link.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Interconnect shape used to wire the simulated nodes together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TopologyKind {
+    /// 2D grid, each node connected to its N/S/E/W neighbors
+    Mesh,
+    /// 2D grid like [`TopologyKind::Mesh`] but with wraparound edges at each boundary
+    Torus,
+    /// Nodes grouped into fully-connected clusters, with one inter-group link per cluster
+    /// providing global connectivity (simplified Dragonfly)
+    Dragonfly,
+}
+
+/// Routing policy used to compute a path between two nodes over a [`Topology`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoutingPolicy {
+    /// Minimum-latency path (Dijkstra over per-link latency)
+    ShortestPath,
+    /// Valiant-style load spreading: splice the shortest paths through a randomly sampled
+    /// intermediate node, biasing the sample away from the most congested candidates, rather
+    /// than always taking the minimum-latency path
+    AdaptiveValiant,
+}
+
+/// A single link's base conditions and current traffic utilization
+#[derive(Debug, Clone, Copy)]
+pub struct LinkState {
+    pub latency_ms: f64,
+    pub bandwidth_kbps: f64,
+    pub packet_loss: f64,
+    pub jitter_ms: f64,
+    /// Fraction of `bandwidth_kbps` currently claimed by routed flows, decayed each tick so
+    /// idle links cool back down rather than accumulating utilization forever
+    pub utilization: f64,
+}
+
+/// Number of candidate intermediates sampled per Valiant routing decision
+const VALIANT_CANDIDATE_COUNT: usize = 4;
+
+/// Per-tick decay applied to link utilization before new flows are added
+const UTILIZATION_DECAY: f64 = 0.85;
+
+/// Weight applied to a hop's packet loss when scoring Valiant candidate paths, relative to
+/// utilization -- high enough that a lossy but idle hop still loses out to a busier clean one
+const VALIANT_LOSS_PENALTY: f64 = 5.0;
+
+/// Nodes per fully-connected cluster in a [`TopologyKind::Dragonfly`] build
+const DRAGONFLY_GROUP_SIZE: usize = 4;
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+/// Structured interconnect of nodes wired per a [`TopologyKind`], with per-link state used both
+/// to aggregate end-to-end conditions for a routed path and to report link utilization
+pub struct Topology {
+    kind: TopologyKind,
+    adjacency: HashMap<usize, Vec<usize>>,
+    links: HashMap<(usize, usize), LinkState>,
+}
+
+impl Topology {
+    /// Build a topology over `node_count` nodes, randomizing each link's base conditions
+    pub fn build(kind: TopologyKind, node_count: usize, rng: &mut impl Rng) -> Self {
+        let mut topology = Self { kind, adjacency: HashMap::new(), links: HashMap::new() };
+        if node_count == 0 {
+            return topology;
+        }
+
+        let edges = match kind {
+            TopologyKind::Mesh => Self::grid_edges(node_count, false),
+            TopologyKind::Torus => Self::grid_edges(node_count, true),
+            TopologyKind::Dragonfly => Self::dragonfly_edges(node_count),
+        };
+
+        for (a, b) in edges {
+            topology.add_edge(a, b, rng);
+        }
+
+        topology
+    }
+
+    fn add_edge(&mut self, a: usize, b: usize, rng: &mut impl Rng) {
+        let key = edge_key(a, b);
+        if self.links.contains_key(&key) {
+            return;
+        }
+
+        self.adjacency.entry(a).or_default().push(b);
+        self.adjacency.entry(b).or_default().push(a);
+
+        self.links.insert(
+            key,
+            LinkState {
+                latency_ms: 5.0 + rng.gen_range(0.0..15.0),
+                bandwidth_kbps: 10_000.0 + rng.gen_range(0.0..40_000.0),
+                packet_loss: rng.gen_range(0.0..0.01),
+                jitter_ms: rng.gen_range(0.0..3.0),
+                utilization: 0.0,
+            },
+        );
+    }
+
+    /// Square-ish grid of `node_count` nodes connected to their N/S/E/W neighbors, wrapping
+    /// around each boundary when `wraparound` is set (torus) or stopping at the edge (mesh)
+    fn grid_edges(node_count: usize, wraparound: bool) -> Vec<(usize, usize)> {
+        let side = (node_count as f64).sqrt().ceil() as usize;
+        let mut edges = Vec::new();
+
+        for id in 0..node_count {
+            let row = (id / side) as isize;
+            let col = (id % side) as isize;
+
+            for (dr, dc) in [(-1isize, 0isize), (1, 0), (0, -1), (0, 1)] {
+                let (nr, nc) = if wraparound {
+                    ((row + dr).rem_euclid(side as isize), (col + dc).rem_euclid(side as isize))
+                } else {
+                    let nr = row + dr;
+                    let nc = col + dc;
+                    if nr < 0 || nc < 0 || nr as usize >= side || nc as usize >= side {
+                        continue;
+                    }
+                    (nr, nc)
+                };
+
+                let neighbor_id = nr as usize * side + nc as usize;
+                if neighbor_id < node_count && neighbor_id != id {
+                    edges.push((id, neighbor_id));
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Nodes grouped into fully-connected clusters of [`DRAGONFLY_GROUP_SIZE`], each cluster
+    /// linked to the next by one inter-group edge, forming a ring of groups
+    fn dragonfly_edges(node_count: usize) -> Vec<(usize, usize)> {
+        let mut edges = Vec::new();
+        let group_count = (node_count + DRAGONFLY_GROUP_SIZE - 1) / DRAGONFLY_GROUP_SIZE;
+
+        for group in 0..group_count {
+            let start = group * DRAGONFLY_GROUP_SIZE;
+            let end = (start + DRAGONFLY_GROUP_SIZE).min(node_count);
+
+            for a in start..end {
+                for b in (a + 1)..end {
+                    edges.push((a, b));
+                }
+            }
+
+            let next_start = ((group + 1) % group_count) * DRAGONFLY_GROUP_SIZE;
+            if group_count > 1 && next_start < node_count && next_start != start {
+                edges.push((start, next_start));
+            }
+        }
+
+        edges
+    }
+
+    pub fn kind(&self) -> TopologyKind {
+        self.kind
+    }
+
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        self.adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn link(&self, a: usize, b: usize) -> Option<&LinkState> {
+        self.links.get(&edge_key(a, b))
+    }
+
+    fn link_mut(&mut self, a: usize, b: usize) -> Option<&mut LinkState> {
+        self.links.get_mut(&edge_key(a, b))
+    }
+
+    /// All links with their endpoints, for utilization reporting
+    pub fn all_links(&self) -> impl Iterator<Item = (&(usize, usize), &LinkState)> {
+        self.links.iter()
+    }
+
+    /// Decay every link's utilization, letting congestion cool down between ticks
+    pub fn decay_utilization(&mut self) {
+        for link in self.links.values_mut() {
+            link.utilization *= UTILIZATION_DECAY;
+        }
+    }
+
+    /// Claim `bandwidth_kbps` worth of traffic on every link along `path`, raising their
+    /// reported utilization
+    pub fn record_traffic(&mut self, path: &[usize], bandwidth_kbps: f64) {
+        for window in path.windows(2) {
+            if let Some(link) = self.link_mut(window[0], window[1]) {
+                link.utilization += bandwidth_kbps / link.bandwidth_kbps.max(1.0);
+            }
+        }
+    }
+
+    /// Compute a path from `source` to `dest` per `policy`, or `None` if unreachable
+    pub fn route(&self, policy: RoutingPolicy, source: usize, dest: usize, rng: &mut impl Rng) -> Option<Vec<usize>> {
+        if source == dest {
+            return Some(vec![source]);
+        }
+
+        match policy {
+            RoutingPolicy::ShortestPath => self.shortest_path(source, dest),
+            RoutingPolicy::AdaptiveValiant => self.valiant_path(source, dest, rng),
+        }
+    }
+
+    /// Dijkstra shortest path weighted by per-link latency
+    fn shortest_path(&self, source: usize, dest: usize) -> Option<Vec<usize>> {
+        #[derive(PartialEq)]
+        struct Candidate {
+            cost: f64,
+            node: usize,
+        }
+        impl Eq for Candidate {}
+        impl Ord for Candidate {
+            fn cmp(&self, other: &Self) -> Ordering {
+                other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+            }
+        }
+        impl PartialOrd for Candidate {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        let mut dist: HashMap<usize, f64> = HashMap::new();
+        let mut prev: HashMap<usize, usize> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        dist.insert(source, 0.0);
+        heap.push(Candidate { cost: 0.0, node: source });
+
+        while let Some(Candidate { cost, node }) = heap.pop() {
+            if node == dest {
+                break;
+            }
+            if cost > *dist.get(&node).unwrap_or(&f64::INFINITY) {
+                continue;
+            }
+
+            for &neighbor in self.neighbors(node) {
+                let link_cost = self.link(node, neighbor).map(|l| l.latency_ms).unwrap_or(1.0);
+                let next_cost = cost + link_cost;
+                if next_cost < *dist.get(&neighbor).unwrap_or(&f64::INFINITY) {
+                    dist.insert(neighbor, next_cost);
+                    prev.insert(neighbor, node);
+                    heap.push(Candidate { cost: next_cost, node: neighbor });
+                }
+            }
+        }
+
+        if !dist.contains_key(&dest) {
+            return None;
+        }
+
+        let mut path = vec![dest];
+        let mut current = dest;
+        while current != source {
+            current = match prev.get(&current) {
+                Some(&node) => node,
+                None => return None,
+            };
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Valiant routing: sample a handful of candidate intermediates, splice the shortest paths
+    /// source -> intermediate -> dest for each, and keep the one crossing the least-congested
+    /// links, spreading load away from hot spots instead of always taking the shortest path
+    fn valiant_path(&self, source: usize, dest: usize, rng: &mut impl Rng) -> Option<Vec<usize>> {
+        let all_nodes: Vec<usize> = self.adjacency.keys().cloned().collect();
+        if all_nodes.is_empty() {
+            return self.shortest_path(source, dest);
+        }
+
+        let mut best: Option<(f64, Vec<usize>)> = None;
+        for _ in 0..VALIANT_CANDIDATE_COUNT {
+            let intermediate = all_nodes[rng.gen_range(0..all_nodes.len())];
+            if intermediate == source || intermediate == dest {
+                continue;
+            }
+
+            let first_leg = self.shortest_path(source, intermediate);
+            let second_leg = self.shortest_path(intermediate, dest);
+            let (first_leg, second_leg) = match (first_leg, second_leg) {
+                (Some(first_leg), Some(second_leg)) => (first_leg, second_leg),
+                _ => continue,
+            };
+
+            let mut path = first_leg;
+            path.extend(second_leg.into_iter().skip(1));
+
+            // Combine congestion and loss into one score so a candidate can't win purely by
+            // routing through a busy-but-clean hop at the expense of a quiet-but-lossy one
+            let score: f64 = path
+                .windows(2)
+                .filter_map(|w| self.link(w[0], w[1]))
+                .map(|l| l.utilization + l.packet_loss * VALIANT_LOSS_PENALTY)
+                .sum();
+
+            let better = match &best {
+                Some((best_score, _)) => score < *best_score,
+                None => true,
+            };
+            if better {
+                best = Some((score, path));
+            }
+        }
+
+        match best {
+            Some((_, path)) => Some(path),
+            None => self.shortest_path(source, dest),
+        }
+    }
+
+    /// End-to-end conditions aggregated over a path's hops: latency and jitter sum, bandwidth is
+    /// the bottleneck (minimum) hop, and packet loss compounds multiplicatively along the path.
+    /// Returns `(latency_ms, bandwidth_kbps, packet_loss, jitter_ms)`.
+    pub fn aggregate_conditions(&self, path: &[usize]) -> Option<(f64, f64, f64, f64)> {
+        if path.len() < 2 {
+            return None;
+        }
+
+        let mut latency_ms = 0.0;
+        let mut bandwidth_kbps = f64::INFINITY;
+        let mut delivery_ratio = 1.0;
+        let mut jitter_ms = 0.0;
+
+        for window in path.windows(2) {
+            let link = self.link(window[0], window[1])?;
+            latency_ms += link.latency_ms;
+            bandwidth_kbps = bandwidth_kbps.min(link.bandwidth_kbps);
+            delivery_ratio *= 1.0 - link.packet_loss;
+            jitter_ms += link.jitter_ms;
+        }
+
+        Some((latency_ms, bandwidth_kbps, 1.0 - delivery_ratio, jitter_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::VecDeque;
+
+    /// Fewest-hop path between `source` and `dest` via BFS over [`Topology::neighbors`], ignoring
+    /// per-link latency entirely -- used by tests that care about topological diameter rather than
+    /// the latency-weighted routing [`Topology::shortest_path`] actually performs.
+    fn fewest_hop_path(topology: &Topology, source: usize, dest: usize) -> Option<Vec<usize>> {
+        let mut prev: HashMap<usize, usize> = HashMap::new();
+        let mut visited: HashMap<usize, bool> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        visited.insert(source, true);
+        queue.push_back(source);
+
+        while let Some(node) = queue.pop_front() {
+            if node == dest {
+                break;
+            }
+            for &neighbor in topology.neighbors(node) {
+                if visited.insert(neighbor, true).is_none() {
+                    prev.insert(neighbor, node);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        if !visited.contains_key(&dest) {
+            return None;
+        }
+
+        let mut path = vec![dest];
+        let mut current = dest;
+        while current != source {
+            current = *prev.get(&current)?;
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[test]
+    fn mesh_topology_connects_every_node() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let topology = Topology::build(TopologyKind::Mesh, 16, &mut rng);
+
+        for node in 0..16 {
+            let path = topology.route(RoutingPolicy::ShortestPath, 0, node, &mut rng);
+            assert!(path.is_some(), "node {} should be reachable from node 0", node);
+        }
+    }
+
+    #[test]
+    fn torus_has_shorter_or_equal_worst_case_hops_than_mesh() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mesh = Topology::build(TopologyKind::Mesh, 25, &mut rng);
+        let torus = Topology::build(TopologyKind::Torus, 25, &mut rng);
+
+        let mesh_path = fewest_hop_path(&mesh, 0, 24).unwrap();
+        let torus_path = fewest_hop_path(&torus, 0, 24).unwrap();
+
+        assert!(torus_path.len() <= mesh_path.len());
+    }
+
+    #[test]
+    fn aggregate_conditions_compound_packet_loss_and_sum_latency() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let topology = Topology::build(TopologyKind::Mesh, 9, &mut rng);
+        let path = topology.route(RoutingPolicy::ShortestPath, 0, 8, &mut rng).unwrap();
+
+        let (latency_ms, bandwidth_kbps, packet_loss, _jitter_ms) =
+            topology.aggregate_conditions(&path).unwrap();
+
+        let hop_count = path.len() - 1;
+        assert!(latency_ms >= hop_count as f64 * 5.0);
+        assert!(bandwidth_kbps > 0.0);
+        assert!((0.0..1.0).contains(&packet_loss));
+    }
+
+    #[test]
+    fn valiant_path_avoids_a_high_loss_candidate_even_when_idle() {
+        // Diamond: 0 -> {1, 2} -> 3, so node 1 and node 2 are the only possible Valiant
+        // intermediates between source 0 and dest 3
+        let mut topology = Topology { kind: TopologyKind::Mesh, adjacency: HashMap::new(), links: HashMap::new() };
+        let mut rng = StdRng::seed_from_u64(42);
+        for (a, b) in [(0, 1), (1, 3), (0, 2), (2, 3)] {
+            topology.add_edge(a, b, &mut rng);
+        }
+
+        // Route through node 1 is idle but lossy; route through node 2 is busier but clean
+        topology.link_mut(0, 1).unwrap().packet_loss = 0.9;
+        topology.link_mut(1, 3).unwrap().packet_loss = 0.9;
+        topology.link_mut(0, 2).unwrap().utilization = 0.3;
+        topology.link_mut(2, 3).unwrap().utilization = 0.3;
+
+        // Sample many seeds rather than asserting on one draw: a single call's candidate
+        // sampling can miss the clean intermediate entirely and fall back to pure-latency
+        // shortest-path, so check that the lossy route wins only rarely, not never
+        let via_node_2 = (0..30)
+            .filter(|&seed| {
+                let path = topology.valiant_path(0, 3, &mut StdRng::seed_from_u64(seed)).unwrap();
+                path == vec![0, 2, 3]
+            })
+            .count();
+
+        assert!(via_node_2 >= 20, "expected the clean path to dominate, got {via_node_2}/30");
+    }
+
+    #[test]
+    fn recording_traffic_raises_utilization_and_decay_cools_it_down() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut topology = Topology::build(TopologyKind::Mesh, 9, &mut rng);
+
+        topology.record_traffic(&[0, 1], 5000.0);
+        let after_traffic = topology.link(0, 1).unwrap().utilization;
+        assert!(after_traffic > 0.0);
+
+        topology.decay_utilization();
+        let after_decay = topology.link(0, 1).unwrap().utilization;
+        assert!(after_decay < after_traffic);
+    }
+}
@@ -0,0 +1,235 @@
+//! Topology and result export to Graphviz DOT and NetJSON: lets a run's simulated network —
+//! nodes, links, and each link's final latency/loss/chosen protocol — be serialized to a
+//! machine-readable graph instead of only scalar CSV/JSON metrics. DOT renders with
+//! `dot -Tsvg`; NetJSON NetworkGraph plugs into existing d3-based network visualizers.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::metrics::ErrorString;
+use super::network::NetworkSimulation;
+
+/// Machine-readable topology export format, selectable via `SimulationConfig::export_formats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TopologyFormat {
+    /// Graphviz DOT, rendered with `dot -Tsvg`
+    Dot,
+    /// NetJSON NetworkGraph, consumed by d3-based network visualizers
+    NetJson,
+}
+
+impl TopologyFormat {
+    fn file_extension(&self) -> &'static str {
+        match self {
+            TopologyFormat::Dot => "dot",
+            TopologyFormat::NetJson => "json",
+        }
+    }
+}
+
+/// Final per-link metrics captured for export: the topology's own base latency/loss plus the
+/// protocol chosen by whichever simulated connection most recently routed traffic over it
+#[derive(Debug, Clone)]
+struct LinkSnapshot {
+    source: usize,
+    dest: usize,
+    latency_ms: f64,
+    packet_loss: f64,
+    protocol: Option<String>,
+}
+
+/// A captured view of the simulated topology and its per-link metrics, ready to be serialized
+/// to any [`TopologyFormat`] without re-reading `simulation`
+pub struct TopologySnapshot {
+    nodes: Vec<(usize, String)>,
+    links: Vec<LinkSnapshot>,
+}
+
+impl TopologySnapshot {
+    /// Capture the current topology, node names, and the protocol most recently chosen for
+    /// each link from `simulation`'s routed connections
+    pub fn capture(simulation: &NetworkSimulation) -> Self {
+        let mut nodes: Vec<(usize, String)> = simulation
+            .get_nodes()
+            .iter()
+            .map(|(&id, node)| (id, node.name().to_string()))
+            .collect();
+        nodes.sort_by_key(|(id, _)| *id);
+
+        let mut protocol_by_link: HashMap<(usize, usize), String> = HashMap::new();
+        for conn in simulation.get_connections() {
+            if let Some(protocol) = &conn.active_protocol {
+                for hop in conn.path.windows(2) {
+                    let key = if hop[0] < hop[1] { (hop[0], hop[1]) } else { (hop[1], hop[0]) };
+                    protocol_by_link.insert(key, protocol.clone());
+                }
+            }
+        }
+
+        let links = simulation
+            .topology_links()
+            .into_iter()
+            .map(|((source, dest), link)| LinkSnapshot {
+                source,
+                dest,
+                latency_ms: link.latency_ms,
+                packet_loss: link.packet_loss,
+                protocol: protocol_by_link.get(&(source, dest)).cloned(),
+            })
+            .collect();
+
+        Self { nodes, links }
+    }
+
+    /// Serialize this snapshot in `format` to `writer`
+    pub fn write_to(&self, format: TopologyFormat, writer: &mut dyn Write) -> Result<(), ErrorString> {
+        match format {
+            TopologyFormat::Dot => self.write_dot(writer),
+            TopologyFormat::NetJson => self.write_netjson(writer),
+        }
+    }
+
+    fn write_dot(&self, writer: &mut dyn Write) -> Result<(), ErrorString> {
+        writeln!(writer, "graph topology {{")?;
+        for (id, name) in &self.nodes {
+            writeln!(writer, "  n{} [label=\"{}\"];", id, name)?;
+        }
+        for link in &self.links {
+            writeln!(
+                writer,
+                "  n{} -- n{} [label=\"{:.1}ms, {:.2}% loss, {}\"];",
+                link.source,
+                link.dest,
+                link.latency_ms,
+                link.packet_loss * 100.0,
+                link.protocol.as_deref().unwrap_or("none"),
+            )?;
+        }
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+
+    fn write_netjson(&self, writer: &mut dyn Write) -> Result<(), ErrorString> {
+        #[derive(Serialize)]
+        struct NetJsonNode {
+            id: String,
+            label: String,
+        }
+
+        #[derive(Serialize)]
+        struct NetJsonLinkProperties {
+            latency_ms: f64,
+            packet_loss: f64,
+            protocol: Option<String>,
+        }
+
+        #[derive(Serialize)]
+        struct NetJsonLink {
+            source: String,
+            target: String,
+            cost: f64,
+            properties: NetJsonLinkProperties,
+        }
+
+        #[derive(Serialize)]
+        struct NetJsonGraph {
+            #[serde(rename = "type")]
+            kind: &'static str,
+            protocol: &'static str,
+            version: &'static str,
+            metric: &'static str,
+            nodes: Vec<NetJsonNode>,
+            links: Vec<NetJsonLink>,
+        }
+
+        let graph = NetJsonGraph {
+            kind: "NetworkGraph",
+            protocol: "static",
+            version: "1.0",
+            metric: "latency_ms",
+            nodes: self
+                .nodes
+                .iter()
+                .map(|(id, name)| NetJsonNode { id: id.to_string(), label: name.clone() })
+                .collect(),
+            links: self
+                .links
+                .iter()
+                .map(|link| NetJsonLink {
+                    source: link.source.to_string(),
+                    target: link.dest.to_string(),
+                    cost: link.latency_ms,
+                    properties: NetJsonLinkProperties {
+                        latency_ms: link.latency_ms,
+                        packet_loss: link.packet_loss,
+                        protocol: link.protocol.clone(),
+                    },
+                })
+                .collect(),
+        };
+
+        let json = serde_json::to_string_pretty(&graph)
+            .map_err(|e| ErrorString(format!("Failed to serialize NetJSON topology snapshot: {}", e)))?;
+        writeln!(writer, "{}", json)?;
+        Ok(())
+    }
+
+    /// Write this snapshot in `format` to `<dir>/topology_snapshot<suffix>.<ext>`, creating
+    /// `dir` if needed. `suffix` distinguishes per-interval snapshots (e.g. `_3`) from the
+    /// single end-of-run export (empty suffix).
+    pub fn export_to_dir(&self, dir: &str, suffix: &str, format: TopologyFormat) -> Result<(), ErrorString> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| ErrorString(format!("Failed to create output directory {}: {}", dir, e)))?;
+        let path = format!("{}/topology_snapshot{}.{}", dir, suffix, format.file_extension());
+        let mut file = File::create(&path)
+            .map_err(|e| ErrorString(format!("Failed to create topology export file {}: {}", path, e)))?;
+        self.write_to(format, &mut file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::topology::{RoutingPolicy, TopologyKind};
+
+    fn simulation() -> NetworkSimulation {
+        let mut simulation = NetworkSimulation::new();
+        simulation
+            .initialize(6, 0.5, TopologyKind::Mesh, RoutingPolicy::ShortestPath, 7)
+            .unwrap();
+        simulation
+    }
+
+    #[test]
+    fn dot_export_includes_every_node_and_link() {
+        let simulation = simulation();
+        let snapshot = TopologySnapshot::capture(&simulation);
+
+        let mut buffer = Vec::new();
+        snapshot.write_to(TopologyFormat::Dot, &mut buffer).unwrap();
+        let dot = String::from_utf8(buffer).unwrap();
+
+        assert!(dot.starts_with("graph topology {"));
+        for (id, _) in &snapshot.nodes {
+            assert!(dot.contains(&format!("n{} [label=", id)));
+        }
+        assert_eq!(dot.matches(" -- ").count(), snapshot.links.len());
+    }
+
+    #[test]
+    fn netjson_export_is_a_networkgraph_with_matching_link_count() {
+        let simulation = simulation();
+        let snapshot = TopologySnapshot::capture(&simulation);
+
+        let mut buffer = Vec::new();
+        snapshot.write_to(TopologyFormat::NetJson, &mut buffer).unwrap();
+        let json = String::from_utf8(buffer).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed["type"], "NetworkGraph");
+        assert_eq!(parsed["links"].as_array().unwrap().len(), snapshot.links.len());
+    }
+}
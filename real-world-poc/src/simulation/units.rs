@@ -0,0 +1,131 @@
+//! Strongly-typed wrappers for the network-condition quantities threaded through
+//! [`super::network::NodeConnection`] and the scenario-application math. Plain `f64` let ms and
+//! seconds, Kbps and bps, and a 0.0-1.0 fraction vs. a 0-100 percentage get mixed up silently;
+//! these newtypes make the unit part of the type instead of a convention callers have to
+//! remember. Each one stores its value in a fixed canonical unit and offers accessors for the
+//! units the surrounding code actually works in (e.g. `Bandwidth` is stored in bits/sec but
+//! scenario math is written in Kbps).
+
+use std::time::Duration;
+
+/// One-way link delay. Always non-negative; stored as a [`Duration`] so it composes with the
+/// rest of the simulation's time handling instead of being yet another bare millisecond count.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Latency(Duration);
+
+impl Latency {
+    /// Build from a millisecond value, clamping any negative input to zero
+    pub fn from_millis_f64(ms: f64) -> Self {
+        Self(Duration::from_secs_f64(ms.max(0.0) / 1000.0))
+    }
+
+    pub fn as_millis_f64(&self) -> f64 {
+        self.0.as_secs_f64() * 1000.0
+    }
+
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+/// Link throughput, stored in bits per second so it lines up with [`super::nodes::NodeType::default_capacity_bps`].
+/// Scenario and protocol math is written in Kbps, so `kbps()`/`from_kbps` are the usual entry points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bandwidth(u64);
+
+impl Bandwidth {
+    /// Build from a Kbps value, clamping any negative input to zero
+    pub fn from_kbps(kbps: f64) -> Self {
+        Self((kbps.max(0.0) * 1000.0) as u64)
+    }
+
+    pub fn from_bps(bps: u64) -> Self {
+        Self(bps)
+    }
+
+    pub fn kbps(&self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+
+    pub fn bps(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Packet loss rate, a fraction in `0.0..=1.0`. Kept distinct from a percentage so `* 100.0` (or
+/// forgetting to) is no longer a silent bug at every call site that records it.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct PacketLoss(f64);
+
+impl PacketLoss {
+    /// Rejects any value outside `0.0..=1.0`
+    pub fn new(fraction: f64) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(format!("packet loss fraction {} out of range 0.0..=1.0", fraction));
+        }
+        Ok(Self(fraction))
+    }
+
+    /// Clamps into `0.0..=1.0` rather than rejecting, for internal pipeline math (variation
+    /// sums, protocol-improvement multipliers) that can drift a hair out of range
+    pub fn clamped(fraction: f64) -> Self {
+        Self(fraction.clamp(0.0, 1.0))
+    }
+
+    pub fn fraction(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_percentage(&self) -> f64 {
+        self.0 * 100.0
+    }
+}
+
+/// Variation in inter-packet delay. Always non-negative; stored as a [`Duration`] for the same
+/// reason as [`Latency`].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Jitter(Duration);
+
+impl Jitter {
+    /// Build from a millisecond value, clamping any negative input to zero
+    pub fn from_millis_f64(ms: f64) -> Self {
+        Self(Duration::from_secs_f64(ms.max(0.0) / 1000.0))
+    }
+
+    pub fn as_millis_f64(&self) -> f64 {
+        self.0.as_secs_f64() * 1000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_round_trips_and_clamps_negative() {
+        assert_eq!(Latency::from_millis_f64(42.0).as_millis_f64(), 42.0);
+        assert_eq!(Latency::from_millis_f64(-5.0).as_millis_f64(), 0.0);
+    }
+
+    #[test]
+    fn bandwidth_converts_between_bps_and_kbps() {
+        let bw = Bandwidth::from_kbps(1500.0);
+        assert_eq!(bw.bps(), 1_500_000);
+        assert_eq!(bw.kbps(), 1500.0);
+    }
+
+    #[test]
+    fn packet_loss_rejects_out_of_range_but_clamp_does_not() {
+        assert!(PacketLoss::new(1.5).is_err());
+        assert!(PacketLoss::new(-0.1).is_err());
+        assert!(PacketLoss::new(0.5).is_ok());
+        assert_eq!(PacketLoss::clamped(1.5).fraction(), 1.0);
+        assert_eq!(PacketLoss::clamped(1.5).as_percentage(), 100.0);
+    }
+
+    #[test]
+    fn jitter_round_trips_and_clamps_negative() {
+        assert_eq!(Jitter::from_millis_f64(7.5).as_millis_f64(), 7.5);
+        assert_eq!(Jitter::from_millis_f64(-1.0).as_millis_f64(), 0.0);
+    }
+}
@@ -2,13 +2,45 @@
 //! Creates visual representations of the network performance improvements
 //! to showcase the impact of dynamic protocol adaptation.
 
-use std::fs::File;
-use std::io::Write;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
 use std::collections::HashMap;
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+use plotters::prelude::*;
+use serde::Serialize;
 
 use super::network::NetworkSimulation;
 use super::metrics::{MetricsCollector, ErrorString};
 
+/// Scenarios rendered in every chart, in display order
+const CHART_SCENARIOS: [&str; 8] = ["ideal", "congestion", "international", "wireless_interference",
+    "mobile_handover", "asymmetric", "satellite", "extreme"];
+
+/// Metrics rendered as their own chart file by [`PerformanceVisualizer::generate_plotters_visualization`]
+const CHART_METRICS: [(&str, &str); 6] = [
+    ("latency", "Latency Reduction (%)"),
+    ("bandwidth", "Bitrate Improvement (%)"),
+    ("throughput", "Throughput Improvement (%)"),
+    ("packet_loss", "Packet Loss Reduction (%)"),
+    ("transfer_time", "Transfer Time Reduction (%)"),
+    ("overall", "Overall Improvement (%)"),
+];
+
+/// Metrics tracked per live `update()` call, charted over time by the time-series view
+const TIMESERIES_METRICS: [(&str, &str); 5] = [
+    ("latency", "Latency Improvement Over Time (%)"),
+    ("bandwidth", "Bitrate Improvement Over Time (%)"),
+    ("throughput", "Throughput Improvement Over Time (%)"),
+    ("transfer_time", "Transfer Time Improvement Over Time (%)"),
+    ("overall", "Overall Improvement Over Time (%)"),
+];
+
+/// Default window size (seconds) used to smooth the time-series view when callers don't pick one
+const DEFAULT_TIMESERIES_WINDOW_SECS: f64 = 5.0;
+
 /// Performance visualizer for network simulation
 pub struct PerformanceVisualizer {
     /// Whether live visualization is enabled
@@ -17,6 +49,72 @@ pub struct PerformanceVisualizer {
     format: VisualizationFormat,
     /// Data points for visualization
     data_points: Vec<VisualizationDataPoint>,
+    /// Recorded protocol/scenario switches, backing the HTML report's adaptation timeline
+    adaptation_events: Vec<AdaptationEvent>,
+    /// Live NDJSON sink, set via [`PerformanceVisualizer::enable_streaming`]
+    stream: Option<StreamSink>,
+}
+
+/// Destination for the live NDJSON metrics stream enabled via
+/// [`PerformanceVisualizer::enable_streaming`], independent of the final CSV/HTML generation
+pub enum StreamTarget {
+    /// Append NDJSON lines to a plain file at this path
+    File(String),
+    /// Write NDJSON lines to a named FIFO (already created with `mkfifo`) at this path
+    Fifo(String),
+    /// Connect to a TCP listener at this address (e.g. `"127.0.0.1:9000"`) and write lines to it
+    Tcp(String),
+}
+
+/// Open streaming destination; file, FIFO and TCP targets all write through the same
+/// line-oriented path, buffering and retrying on `WouldBlock` so a slow reader never sees a
+/// corrupted partial line (the hamnet70 visualizer's one-message-per-line convention)
+struct StreamSink {
+    writer: Box<dyn Write + Send>,
+}
+
+impl StreamSink {
+    fn open(target: &StreamTarget) -> Result<Self, ErrorString> {
+        let writer: Box<dyn Write + Send> = match target {
+            StreamTarget::File(path) => Box::new(
+                OpenOptions::new().create(true).append(true).open(path)
+                    .map_err(|e| ErrorString(format!("Failed to open stream file {}: {}", path, e)))?
+            ),
+            StreamTarget::Fifo(path) => Box::new(
+                OpenOptions::new().write(true).open(path)
+                    .map_err(|e| ErrorString(format!("Failed to open stream fifo {}: {}", path, e)))?
+            ),
+            StreamTarget::Tcp(addr) => Box::new(
+                TcpStream::connect(addr)
+                    .map_err(|e| ErrorString(format!("Failed to connect stream socket {}: {}", addr, e)))?
+            ),
+        };
+        Ok(Self { writer })
+    }
+
+    /// Write one NDJSON record as a single line, retrying on `WouldBlock` instead of leaving
+    /// a partial line for the reader to choke on. A hard write error drops the record rather
+    /// than halting the simulation over a dead reader.
+    fn write_line(&mut self, line: &str) {
+        let bytes = format!("{}\n", line).into_bytes();
+        let mut written = 0;
+        let mut backoff = Duration::from_millis(1);
+        while written < bytes.len() {
+            match self.writer.write(&bytes[written..]) {
+                Ok(0) => break,
+                Ok(n) => {
+                    written += n;
+                    backoff = Duration::from_millis(1);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    std::thread::sleep(backoff);
+                    backoff = (backoff * 2).min(Duration::from_millis(100));
+                }
+                Err(_) => break,
+            }
+        }
+        let _ = self.writer.flush();
+    }
 }
 
 /// Visualization format
@@ -26,10 +124,14 @@ pub enum VisualizationFormat {
     Csv,
     /// HTML+JS visualization
     Html,
+    /// Server-side rendered PNG image(s)
+    Png,
+    /// Server-side rendered SVG image(s)
+    Svg,
 }
 
 /// Data point for visualization
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 struct VisualizationDataPoint {
     /// Timestamp in seconds
     timestamp: f64,
@@ -45,6 +147,19 @@ struct VisualizationDataPoint {
     improvement: f64,
 }
 
+/// One interval of constant protocol/scenario activity on the adaptation timeline, recorded
+/// by [`PerformanceVisualizer::update`] whenever the scenario or the dominant active protocol
+/// model (see [`MetricsCollector::most_used_model`]) changes
+#[derive(Debug, Clone, Serialize)]
+struct AdaptationEvent {
+    /// Timestamp this interval began (seconds, same clock as [`VisualizationDataPoint::timestamp`])
+    start: f64,
+    /// Scenario active during this interval
+    scenario: String,
+    /// Protocol model most in use during this interval
+    protocol: String,
+}
+
 impl PerformanceVisualizer {
     /// Create a new performance visualizer
     pub fn new() -> Self {
@@ -52,13 +167,25 @@ impl PerformanceVisualizer {
             live_enabled: false,
             format: VisualizationFormat::Html,
             data_points: Vec::new(),
+            adaptation_events: Vec::new(),
+            stream: None,
         }
     }
-    
+
+    /// Enable the live NDJSON metrics stream, independent of the final CSV/HTML generation.
+    /// Every subsequent `update()` call serializes its new data points as one JSON object per
+    /// line and writes them to `target`, flushing after each record so a reader can parse
+    /// incrementally while the simulation is still running.
+    pub fn enable_streaming(&mut self, target: StreamTarget) -> Result<(), ErrorString> {
+        self.stream = Some(StreamSink::open(&target)?);
+        Ok(())
+    }
+
     /// Initialize the visualizer
     pub fn initialize(&mut self, live_enabled: bool) {
         self.live_enabled = live_enabled;
         self.data_points.clear();
+        self.adaptation_events.clear();
     }
     
     /// Check if live visualization is enabled
@@ -84,36 +211,61 @@ impl PerformanceVisualizer {
             .map(|s| s.name.clone())
             .unwrap_or_else(|| "unknown".to_string());
             
+        // Record a new timeline interval whenever the scenario or the dominant active
+        // protocol changes, rather than on every tick
+        let protocol = metrics.most_used_model();
+        let is_new_interval = match self.adaptation_events.last() {
+            Some(last) => last.scenario != scenario_name || last.protocol != protocol,
+            None => true,
+        };
+        if is_new_interval {
+            self.adaptation_events.push(AdaptationEvent {
+                start: timestamp,
+                scenario: scenario_name.clone(),
+                protocol,
+            });
+        }
+
         // Add some data points for the current state
         let improvement = metrics.calculate_scenario_improvement(&scenario_name);
-        
+        let (baseline, adapted) = metrics.scenario_metrics(&scenario_name);
+
         self.data_points.push(VisualizationDataPoint {
             timestamp,
             scenario: scenario_name.clone(),
             metric: "latency".to_string(),
-            baseline: 0.0, // Will be filled in later
-            with_adaptation: 0.0, // Will be filled in later
+            baseline: baseline.map(|m| m.avg_latency).unwrap_or(0.0),
+            with_adaptation: adapted.map(|m| m.avg_latency).unwrap_or(0.0),
             improvement: improvement.latency,
         });
-        
+
         self.data_points.push(VisualizationDataPoint {
             timestamp,
             scenario: scenario_name.clone(),
             metric: "bandwidth".to_string(),
-            baseline: 0.0,
-            with_adaptation: 0.0,
+            baseline: baseline.map(|m| m.avg_bandwidth).unwrap_or(0.0),
+            with_adaptation: adapted.map(|m| m.avg_bandwidth).unwrap_or(0.0),
             improvement: improvement.bandwidth,
         });
-        
+
+        self.data_points.push(VisualizationDataPoint {
+            timestamp,
+            scenario: scenario_name.clone(),
+            metric: "throughput".to_string(),
+            baseline: baseline.map(|m| m.avg_throughput).unwrap_or(0.0),
+            with_adaptation: adapted.map(|m| m.avg_throughput).unwrap_or(0.0),
+            improvement: improvement.throughput,
+        });
+
         self.data_points.push(VisualizationDataPoint {
             timestamp,
             scenario: scenario_name.clone(),
             metric: "transfer_time".to_string(),
-            baseline: 0.0,
-            with_adaptation: 0.0,
+            baseline: baseline.map(|m| m.avg_transfer_time).unwrap_or(0.0),
+            with_adaptation: adapted.map(|m| m.avg_transfer_time).unwrap_or(0.0),
             improvement: improvement.transfer_time,
         });
-        
+
         self.data_points.push(VisualizationDataPoint {
             timestamp,
             scenario: scenario_name,
@@ -122,16 +274,35 @@ impl PerformanceVisualizer {
             with_adaptation: 0.0,
             improvement: improvement.overall,
         });
+
+        // Stream the points just pushed (latency, bandwidth, throughput, transfer_time,
+        // overall) as NDJSON
+        if self.stream.is_some() {
+            let new_points = self.data_points[self.data_points.len() - 5..].to_vec();
+            let sink = self.stream.as_mut().unwrap();
+            for point in &new_points {
+                if let Ok(line) = serde_json::to_string(point) {
+                    sink.write_line(&line);
+                }
+            }
+        }
     }
     
-    /// Generate final visualizations
+    /// Generate final visualizations, smoothing the time-series view with the default window
     pub fn generate_final_visualizations(&self, simulation: &NetworkSimulation, metrics: &MetricsCollector) -> Result<(), ErrorString> {
+        self.generate_final_visualizations_windowed(simulation, metrics, DEFAULT_TIMESERIES_WINDOW_SECS)
+    }
+
+    /// Generate final visualizations, bucketing the live time-series data into `window_secs`
+    /// windows (see [`Self::windowed_improvement`]) so noisy high-frequency samples read as
+    /// trend lines rather than a jagged scatter.
+    pub fn generate_final_visualizations_windowed(&self, simulation: &NetworkSimulation, metrics: &MetricsCollector, window_secs: f64) -> Result<(), ErrorString> {
         // Generate CSV data
         self.generate_csv_data(simulation, metrics)?;
-        
+
         // Generate HTML visualization
-        self.generate_html_visualization(simulation, metrics)?;
-        
+        self.generate_html_visualization(simulation, metrics, window_secs)?;
+
         Ok(())
     }
     
@@ -167,13 +338,20 @@ impl PerformanceVisualizer {
                 return Err(ErrorString(format!("Failed to write to CSV file: {}", e)));
             }
                 
-            // Write bandwidth data 
-            let line = format!("{},Bandwidth,100.0,{:.2},{:.2}\n", 
+            // Write bitrate data (configured, not achieved - see Throughput below)
+            let line = format!("{},Bandwidth,100.0,{:.2},{:.2}\n",
                 scenario_name, 100.0 + improvement.bandwidth, improvement.bandwidth);
             if let Err(e) = file.write_all(line.as_bytes()) {
                 return Err(ErrorString(format!("Failed to write to CSV file: {}", e)));
             }
-                
+
+            // Write throughput data (achieved goodput, derated from bitrate by packet loss)
+            let line = format!("{},Throughput,100.0,{:.2},{:.2}\n",
+                scenario_name, 100.0 + improvement.throughput, improvement.throughput);
+            if let Err(e) = file.write_all(line.as_bytes()) {
+                return Err(ErrorString(format!("Failed to write to CSV file: {}", e)));
+            }
+
             // Write packet loss data
             let line = format!("{},PacketLoss,100.0,{:.2},{:.2}\n", 
                 scenario_name, 100.0 - improvement.packet_loss, improvement.packet_loss);
@@ -202,7 +380,7 @@ impl PerformanceVisualizer {
     }
     
     /// Generate HTML visualization with interactive charts
-    fn generate_html_visualization(&self, _simulation: &NetworkSimulation, metrics: &MetricsCollector) -> Result<(), ErrorString> {
+    fn generate_html_visualization(&self, _simulation: &NetworkSimulation, metrics: &MetricsCollector, window_secs: f64) -> Result<(), ErrorString> {
         // Create output file
         let filename = format!("dynamic_protocol_visualization_{}.html", 
             std::time::SystemTime::now()
@@ -221,14 +399,21 @@ impl PerformanceVisualizer {
                          "mobile_handover", "asymmetric", "satellite", "extreme"];
                          
         let mut scenario_data = HashMap::new();
-        
+        let mut bitrate_throughput_data = HashMap::new();
+
         for &scenario_name in &scenarios {
             let improvement = metrics.calculate_scenario_improvement(scenario_name);
             scenario_data.insert(scenario_name.to_string(), improvement);
+
+            let (_, adapted) = metrics.scenario_metrics(scenario_name);
+            bitrate_throughput_data.insert(
+                scenario_name.to_string(),
+                (adapted.map(|m| m.avg_bandwidth).unwrap_or(0.0), adapted.map(|m| m.avg_throughput).unwrap_or(0.0)),
+            );
         }
-        
+
         // Create HTML with embedded JS charts
-        let html = self.generate_html_content(&scenario_data);
+        let html = self.generate_html_content(&scenario_data, &bitrate_throughput_data, window_secs);
         
         // Write to file
         if let Err(e) = file.write_all(html.as_bytes()) {
@@ -241,12 +426,519 @@ impl PerformanceVisualizer {
         Ok(())
     }
     
-    /// Generate HTML content with embedded charts
-    fn generate_html_content(&self, scenario_data: &HashMap<String, super::metrics::PerformanceImprovement>) -> String {
+    /// Render per-scenario bar charts and a multi-dimensional radar comparison as native
+    /// PNG or SVG files, so a publication-ready figure can be produced without a browser.
+    ///
+    /// Each metric (latency, bandwidth, throughput, packet loss, transfer time, overall)
+    /// becomes its own chart file inside `output_dir`, named `<metric>.png`/`.svg`. A final
+    /// `radar_comparison.<ext>` overlays every scenario across five of them (all but
+    /// throughput), a
+    /// `bitrate_vs_throughput.<ext>` grouped bar chart overlays configured bitrate against
+    /// achieved throughput per scenario (raw Kbps, not an improvement percentage), and a
+    /// `timeseries_<metric>.<ext>` line chart is rendered per tracked metric, bucketed into
+    /// `window_secs` windows the same way as the HTML time-series view (see
+    /// [`Self::windowed_improvement`]).
+    pub fn generate_plotters_visualization(&self, output_dir: &str, _simulation: &NetworkSimulation, metrics: &MetricsCollector, window_secs: f64) -> Result<(), ErrorString> {
+        let use_svg = matches!(self.format, VisualizationFormat::Svg);
+        if !use_svg && !matches!(self.format, VisualizationFormat::Png) {
+            return Err(ErrorString("generate_plotters_visualization requires VisualizationFormat::Png or Svg".to_string()));
+        }
+
+        std::fs::create_dir_all(output_dir)
+            .map_err(|e| ErrorString(format!("Failed to create output directory {}: {}", output_dir, e)))?;
+
+        let mut scenario_data = HashMap::new();
+        for &scenario_name in &CHART_SCENARIOS {
+            scenario_data.insert(scenario_name.to_string(), metrics.calculate_scenario_improvement(scenario_name));
+        }
+
+        for (metric_key, metric_title) in &CHART_METRICS {
+            let values: Vec<f64> = CHART_SCENARIOS.iter()
+                .map(|name| Self::metric_value(&scenario_data[*name], metric_key))
+                .collect();
+            let path = Path::new(output_dir).join(format!("{}.{}", metric_key, if use_svg { "svg" } else { "png" }));
+            self.render_bar_chart(&path, use_svg, metric_title, &values)?;
+        }
+
+        let radar_path = Path::new(output_dir).join(format!("radar_comparison.{}", if use_svg { "svg" } else { "png" }));
+        self.render_radar_chart(&radar_path, use_svg, &scenario_data)?;
+
+        let bitrates: Vec<f64> = CHART_SCENARIOS.iter()
+            .map(|name| metrics.scenario_metrics(name).1.map(|m| m.avg_bandwidth).unwrap_or(0.0))
+            .collect();
+        let throughputs: Vec<f64> = CHART_SCENARIOS.iter()
+            .map(|name| metrics.scenario_metrics(name).1.map(|m| m.avg_throughput).unwrap_or(0.0))
+            .collect();
+        let bitrate_path = Path::new(output_dir).join(format!("bitrate_vs_throughput.{}", if use_svg { "svg" } else { "png" }));
+        self.render_bitrate_throughput_chart(&bitrate_path, use_svg, &bitrates, &throughputs)?;
+
+        for (metric_key, metric_title) in &TIMESERIES_METRICS {
+            let series = self.windowed_improvement(metric_key, window_secs, false);
+            let path = Path::new(output_dir).join(format!("timeseries_{}.{}", metric_key, if use_svg { "svg" } else { "png" }));
+            self.render_timeseries_chart(&path, use_svg, metric_title, &series)?;
+        }
+
+        println!("Plotters visualization saved to {}", output_dir);
+
+        Ok(())
+    }
+
+    /// Pull a named metric out of a [`super::metrics::PerformanceImprovement`]
+    fn metric_value(improvement: &super::metrics::PerformanceImprovement, metric_key: &str) -> f64 {
+        match metric_key {
+            "latency" => improvement.latency,
+            "bandwidth" => improvement.bandwidth,
+            "throughput" => improvement.throughput,
+            "packet_loss" => improvement.packet_loss,
+            "transfer_time" => improvement.transfer_time,
+            _ => improvement.overall,
+        }
+    }
+
+    /// Render a single bar chart (one bar per scenario) to the given path
+    fn render_bar_chart(&self, path: &Path, use_svg: bool, title: &str, values: &[f64]) -> Result<(), ErrorString> {
+        let min_value = values.iter().cloned().fold(0.0_f64, f64::min).min(0.0);
+        let max_value = values.iter().cloned().fold(0.0_f64, f64::max).max(1.0) * 1.1;
+
+        let draw = |root: DrawingArea<_, _>| -> Result<(), String> {
+            root.fill(&WHITE).map_err(|e| e.to_string())?;
+            let mut chart = ChartBuilder::on(&root)
+                .caption(title, ("sans-serif", 24))
+                .margin(20)
+                .x_label_area_size(40)
+                .y_label_area_size(50)
+                .build_cartesian_2d((0..CHART_SCENARIOS.len()).into_segmented(), min_value..max_value)
+                .map_err(|e| e.to_string())?;
+
+            chart.configure_mesh()
+                .x_labels(CHART_SCENARIOS.len())
+                .x_label_formatter(&|idx| match idx {
+                    SegmentValue::CenterOf(i) | SegmentValue::Exact(i) => CHART_SCENARIOS.get(*i).unwrap_or(&"").to_string(),
+                    SegmentValue::Last => String::new(),
+                })
+                .y_desc("Improvement %")
+                .draw()
+                .map_err(|e| e.to_string())?;
+
+            chart.draw_series(values.iter().enumerate().map(|(i, &v)| {
+                let mut bar = Rectangle::new(
+                    [(SegmentValue::Exact(i), 0.0), (SegmentValue::Exact(i + 1), v)],
+                    BLUE.filled(),
+                );
+                bar.set_margin(0, 0, 5, 5);
+                bar
+            })).map_err(|e| e.to_string())?;
+
+            Ok(())
+        };
+
+        if use_svg {
+            let root = SVGBackend::new(path, (800, 500)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        } else {
+            let root = BitMapBackend::new(path, (800, 500)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        }
+    }
+
+    /// Render configured bitrate against achieved throughput per scenario, two bars per
+    /// scenario rather than a single improvement percentage, so over-driving a degraded link
+    /// (bitrate up, throughput flat) reads differently from genuine goodput gains (as ALVR's
+    /// statistics tab splits these two series rather than reporting one combined figure)
+    fn render_bitrate_throughput_chart(&self, path: &Path, use_svg: bool, bitrates: &[f64], throughputs: &[f64]) -> Result<(), ErrorString> {
+        let max_value = bitrates.iter().chain(throughputs.iter()).cloned().fold(0.0_f64, f64::max).max(1.0) * 1.1;
+
+        let draw = |root: DrawingArea<_, _>| -> Result<(), String> {
+            root.fill(&WHITE).map_err(|e| e.to_string())?;
+            let mut chart = ChartBuilder::on(&root)
+                .caption("Configured Bitrate vs. Achieved Throughput", ("sans-serif", 24))
+                .margin(20)
+                .x_label_area_size(40)
+                .y_label_area_size(50)
+                .build_cartesian_2d((0..CHART_SCENARIOS.len()).into_segmented(), 0.0..max_value)
+                .map_err(|e| e.to_string())?;
+
+            chart.configure_mesh()
+                .x_labels(CHART_SCENARIOS.len())
+                .x_label_formatter(&|idx| match idx {
+                    SegmentValue::CenterOf(i) | SegmentValue::Exact(i) => CHART_SCENARIOS.get(*i).unwrap_or(&"").to_string(),
+                    SegmentValue::Last => String::new(),
+                })
+                .y_desc("Kbps")
+                .draw()
+                .map_err(|e| e.to_string())?;
+
+            chart.draw_series(bitrates.iter().enumerate().map(|(i, &v)| {
+                let mut bar = Rectangle::new(
+                    [(SegmentValue::Exact(i), 0.0), (SegmentValue::CenterOf(i), v)],
+                    BLUE.filled(),
+                );
+                bar.set_margin(0, 0, 5, 2);
+                bar
+            })).map_err(|e| e.to_string())?
+                .label("Configured bitrate")
+                .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], BLUE.filled()));
+
+            chart.draw_series(throughputs.iter().enumerate().map(|(i, &v)| {
+                let mut bar = Rectangle::new(
+                    [(SegmentValue::CenterOf(i), 0.0), (SegmentValue::Exact(i + 1), v)],
+                    RED.filled(),
+                );
+                bar.set_margin(0, 0, 2, 5);
+                bar
+            })).map_err(|e| e.to_string())?
+                .label("Achieved throughput")
+                .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], RED.filled()));
+
+            chart.configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()
+                .map_err(|e| e.to_string())?;
+
+            Ok(())
+        };
+
+        if use_svg {
+            let root = SVGBackend::new(path, (800, 500)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        } else {
+            let root = BitMapBackend::new(path, (800, 500)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        }
+    }
+
+    /// Render a single windowed time-series line chart (improvement vs. timestamp) to the given path
+    fn render_timeseries_chart(&self, path: &Path, use_svg: bool, title: &str, series: &[(f64, f64)]) -> Result<(), ErrorString> {
+        let (min_t, max_t) = series.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(t, _)| (lo.min(t), hi.max(t)));
+        let (min_t, max_t) = if series.is_empty() { (0.0, 1.0) } else { (min_t, (max_t + 1.0)) };
+        let min_value = series.iter().map(|&(_, v)| v).fold(0.0_f64, f64::min).min(0.0);
+        let max_value = series.iter().map(|&(_, v)| v).fold(0.0_f64, f64::max).max(1.0) * 1.1;
+
+        let draw = |root: DrawingArea<_, _>| -> Result<(), String> {
+            root.fill(&WHITE).map_err(|e| e.to_string())?;
+            let mut chart = ChartBuilder::on(&root)
+                .caption(title, ("sans-serif", 24))
+                .margin(20)
+                .x_label_area_size(40)
+                .y_label_area_size(50)
+                .build_cartesian_2d(min_t..max_t, min_value..max_value)
+                .map_err(|e| e.to_string())?;
+
+            chart.configure_mesh()
+                .x_desc("Time (s)")
+                .y_desc("Improvement %")
+                .draw()
+                .map_err(|e| e.to_string())?;
+
+            chart.draw_series(LineSeries::new(series.iter().cloned(), &BLUE))
+                .map_err(|e| e.to_string())?;
+            chart.draw_series(series.iter().map(|&(t, v)| Circle::new((t, v), 3, BLUE.filled())))
+                .map_err(|e| e.to_string())?;
+
+            Ok(())
+        };
+
+        if use_svg {
+            let root = SVGBackend::new(path, (800, 500)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        } else {
+            let root = BitMapBackend::new(path, (800, 500)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        }
+    }
+
+    /// Render the multi-dimensional radar comparison across all scenarios
+    fn render_radar_chart(&self, path: &Path, use_svg: bool, scenario_data: &HashMap<String, super::metrics::PerformanceImprovement>) -> Result<(), ErrorString> {
+        // Plotters has no built-in radar series, so we draw axes/polygons manually on a
+        // cartesian canvas, one polygon per scenario, labelled by the five metric axes.
+        let axes = ["Latency", "Bandwidth", "Packet Loss", "Transfer Time", "Overall"];
+        let colors = [&BLUE, &RED, &GREEN, &MAGENTA, &CYAN, &BLACK, &RGBColor(255, 140, 0), &RGBColor(128, 0, 128)];
+
+        let draw = |root: DrawingArea<_, _>| -> Result<(), String> {
+            root.fill(&WHITE).map_err(|e| e.to_string())?;
+            let mut chart = ChartBuilder::on(&root)
+                .caption("Multi-dimensional Performance Comparison", ("sans-serif", 24))
+                .margin(30)
+                .build_cartesian_2d(-60.0..60.0, -60.0..60.0)
+                .map_err(|e| e.to_string())?;
+            chart.configure_mesh().disable_mesh().draw().map_err(|e| e.to_string())?;
+
+            let axis_point = |axis_idx: usize, value: f64| -> (f64, f64) {
+                let angle = std::f64::consts::PI * 2.0 * axis_idx as f64 / axes.len() as f64 - std::f64::consts::FRAC_PI_2;
+                let radius = value.max(0.0).min(50.0);
+                (radius * angle.cos(), radius * angle.sin())
+            };
+
+            for (i, &axis_name) in axes.iter().enumerate() {
+                let (x, y) = axis_point(i, 50.0);
+                chart.draw_series(std::iter::once(PathElement::new(vec![(0.0, 0.0), (x, y)], BLACK.mix(0.3))))
+                    .map_err(|e| e.to_string())?;
+                chart.draw_series(std::iter::once(Text::new(axis_name, (x * 1.05, y * 1.05), ("sans-serif", 14))))
+                    .map_err(|e| e.to_string())?;
+            }
+
+            for (i, &scenario_name) in CHART_SCENARIOS.iter().enumerate() {
+                let improvement = &scenario_data[scenario_name];
+                let values = [improvement.latency, improvement.bandwidth, improvement.packet_loss, improvement.transfer_time, improvement.overall];
+                let points: Vec<(f64, f64)> = values.iter().enumerate().map(|(axis_idx, &v)| axis_point(axis_idx, v)).collect();
+                let mut polygon: Vec<(f64, f64)> = points.clone();
+                polygon.push(points[0]);
+                let color = colors[i % colors.len()];
+                chart.draw_series(std::iter::once(PathElement::new(polygon, color)))
+                    .map_err(|e| e.to_string())?
+                    .label(scenario_name)
+                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+            }
+
+            chart.configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()
+                .map_err(|e| e.to_string())?;
+
+            Ok(())
+        };
+
+        if use_svg {
+            let root = SVGBackend::new(path, (800, 800)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        } else {
+            let root = BitMapBackend::new(path, (800, 800)).into_drawing_area();
+            draw(root).map_err(|e| ErrorString(format!("Failed to render {}: {}", path.display(), e)))
+        }
+    }
+
+    /// Bucket raw `data_points` for a single metric into fixed-size time windows, modeled on
+    /// caligula's `ByteSeries::speeds`: samples are grouped into `ceil(t_max / window_secs) + 1`
+    /// bins of `[i*window_secs, (i+1)*window_secs)` relative to the first sample. Each
+    /// non-empty bin emits the mean improvement, or, when `as_rate` is set, the rate of
+    /// change (delta / window_secs) across the bin; empty bins are skipped rather than
+    /// charted as misleading zeros. Returns `(bin_start_secs, value)` pairs in time order.
+    fn windowed_improvement(&self, metric: &str, window_secs: f64, as_rate: bool) -> Vec<(f64, f64)> {
+        if window_secs <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut points: Vec<&VisualizationDataPoint> = self.data_points.iter()
+            .filter(|p| p.metric == metric)
+            .collect();
+        if points.is_empty() {
+            return Vec::new();
+        }
+        points.sort_by(|a, b| a.timestamp.partial_cmp(&b.timestamp).unwrap());
+
+        let t0 = points[0].timestamp;
+        let t_max = points.last().unwrap().timestamp - t0;
+        let bin_count = (t_max / window_secs).ceil() as usize + 1;
+
+        let mut bins: Vec<Vec<f64>> = vec![Vec::new(); bin_count];
+        for point in &points {
+            let idx = (((point.timestamp - t0) / window_secs).floor() as usize).min(bin_count - 1);
+            bins[idx].push(point.improvement);
+        }
+
+        bins.iter().enumerate().filter_map(|(i, values)| {
+            if values.is_empty() {
+                return None;
+            }
+            let bin_start = t0 + i as f64 * window_secs;
+            let value = if as_rate {
+                (values.last().unwrap() - values.first().unwrap()) / window_secs
+            } else {
+                values.iter().sum::<f64>() / values.len() as f64
+            };
+            Some((bin_start, value))
+        }).collect()
+    }
+
+    /// Materialize the recorded switch points into closed `[start, end]` intervals, each
+    /// ending at the next switch (or the most recent sample timestamp for the still-active
+    /// interval)
+    fn adaptation_intervals(&self) -> Vec<(f64, f64, String, String)> {
+        if self.adaptation_events.is_empty() {
+            return Vec::new();
+        }
+
+        let last_timestamp = self.data_points.iter()
+            .map(|p| p.timestamp)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        self.adaptation_events.iter().enumerate().map(|(i, event)| {
+            let end = self.adaptation_events.get(i + 1)
+                .map(|next| next.start)
+                .unwrap_or(last_timestamp)
+                .max(event.start);
+            (event.start, end, event.scenario.clone(), event.protocol.clone())
+        }).collect()
+    }
+
+    /// Build the canvas markup and Chart.js script for the adaptation timeline: a horizontal
+    /// floating-bar "Gantt" chart, one row per protocol, with a bar per recorded interval. On
+    /// cargo's model of a timing report made of overlapping spans, hovering a bar highlights
+    /// the metric samples collected during that interval across the time-series charts built
+    /// by [`Self::generate_timeseries_section`] so a spike can be traced back to the
+    /// adaptation decision that caused it.
+    fn generate_timeline_section(&self) -> (String, String) {
+        let intervals = self.adaptation_intervals();
+        if intervals.is_empty() {
+            return (String::new(), String::new());
+        }
+
+        let mut entries = String::new();
+        for (i, (start, end, scenario, protocol)) in intervals.iter().enumerate() {
+            if i > 0 {
+                entries.push_str(", ");
+            }
+            entries.push_str(&format!(
+                "{{ start: {:.2}, end: {:.2}, scenario: '{}', protocol: '{}' }}",
+                start, end, scenario, protocol
+            ));
+        }
+
+        let canvases = "        <div class=\"chart-item timeline-item\">\n            <canvas id=\"adaptationTimelineChart\"></canvas>\n        </div>\n".to_string();
+
+        let script = format!(r#"
+        const adaptationIntervals = [{entries}];
+        const timelineProtocols = [...new Set(adaptationIntervals.map(iv => iv.protocol))];
+        const timelineDatasets = timelineProtocols.map((protocol, idx) => ({{
+            label: protocol,
+            data: adaptationIntervals.filter(iv => iv.protocol === protocol).map(iv => ({{
+                x: [iv.start, iv.end],
+                y: protocol,
+                scenario: iv.scenario,
+                start: iv.start,
+                end: iv.end,
+            }})),
+            backgroundColor: chartColors[idx % chartColors.length],
+            borderColor: chartColors[idx % chartColors.length].replace('0.7', '1'),
+            borderWidth: 1,
+            barPercentage: 0.6,
+        }}));
+
+        const adaptationTimelineCtx = document.getElementById('adaptationTimelineChart').getContext('2d');
+        const adaptationTimelineChart = new Chart(adaptationTimelineCtx, {{
+            type: 'bar',
+            data: {{ datasets: timelineDatasets }},
+            options: {{
+                indexAxis: 'y',
+                responsive: true,
+                plugins: {{
+                    title: {{
+                        display: true,
+                        text: 'Protocol Adaptation Timeline',
+                        font: {{ size: 16 }}
+                    }},
+                    tooltip: {{
+                        callbacks: {{
+                            label: (ctx) => {{
+                                const d = ctx.raw;
+                                return `${{ctx.dataset.label}} — scenario: ${{d.scenario}} (${{d.start.toFixed(0)}}s-${{d.end.toFixed(0)}}s)`;
+                            }}
+                        }}
+                    }}
+                }},
+                scales: {{
+                    x: {{ title: {{ display: true, text: 'Time (s)' }} }},
+                    y: {{ title: {{ display: true, text: 'Protocol' }} }}
+                }},
+                onHover: (_event, elements) => {{
+                    if (elements.length > 0) {{
+                        const el = elements[0];
+                        const point = timelineDatasets[el.datasetIndex].data[el.index];
+                        highlightInterval(point.start, point.end);
+                    }} else {{
+                        clearHighlight();
+                    }}
+                }}
+            }}
+        }});
+"#,
+            entries = entries,
+        );
+
+        (canvases, script)
+    }
+
+    /// Build the canvas markup and Chart.js script for the windowed time-series view, one
+    /// line chart per tracked metric, plotting improvement against `timestamp`
+    fn generate_timeseries_section(&self, window_secs: f64) -> (String, String) {
+        let mut canvases = String::new();
+        let mut script = String::new();
+
+        for (i, &(metric_key, title)) in TIMESERIES_METRICS.iter().enumerate() {
+            let canvas_id = format!("timeseries{}Chart", metric_key);
+            canvases.push_str(&format!(
+                "        <div class=\"chart-item\">\n            <canvas id=\"{}\"></canvas>\n        </div>\n",
+                canvas_id
+            ));
+
+            let series = self.windowed_improvement(metric_key, window_secs, false);
+            let labels: Vec<String> = series.iter().map(|(t, _)| format!("{:.0}", t)).collect();
+            let values: Vec<String> = series.iter().map(|(_, v)| format!("{:.2}", v)).collect();
+            let times: Vec<String> = series.iter().map(|(t, _)| format!("{:.2}", t)).collect();
+
+            script.push_str(&format!(r#"
+        const {canvas_id}Ctx = document.getElementById('{canvas_id}').getContext('2d');
+        const {canvas_id} = new Chart({canvas_id}Ctx, {{
+            type: 'line',
+            data: {{
+                labels: [{labels}],
+                datasets: [{{
+                    label: '{title}',
+                    data: [{values}],
+                    fill: false,
+                    borderColor: chartColors[{color_idx}],
+                    pointBackgroundColor: chartColors[{color_idx}],
+                    tension: 0.2
+                }}]
+            }},
+            options: {{
+                responsive: true,
+                plugins: {{
+                    title: {{
+                        display: true,
+                        text: '{title} ({window}s windows)',
+                        font: {{ size: 16 }}
+                    }}
+                }},
+                scales: {{
+                    x: {{ title: {{ display: true, text: 'Time (s)' }} }},
+                    y: {{ title: {{ display: true, text: 'Improvement %' }} }}
+                }}
+            }}
+        }});
+        {canvas_id}.rawTimes = [{times}];
+        timeseriesCharts['{metric_key}'] = {canvas_id};
+"#,
+                canvas_id = canvas_id,
+                labels = labels.join(", "),
+                values = values.join(", "),
+                times = times.join(", "),
+                title = title,
+                color_idx = i,
+                window = window_secs,
+                metric_key = metric_key,
+            ));
+        }
+
+        (canvases, script)
+    }
+
+    /// Generate HTML content with embedded charts. `bitrate_throughput_data` carries the raw
+    /// `(bitrate_kbps, throughput_kbps)` pair per scenario for the overlay chart, since the
+    /// improvement percentages in `scenario_data` can't show how far the two have diverged.
+    fn generate_html_content(
+        &self,
+        scenario_data: &HashMap<String, super::metrics::PerformanceImprovement>,
+        bitrate_throughput_data: &HashMap<String, (f64, f64)>,
+        window_secs: f64,
+    ) -> String {
         // Create data arrays for JavaScript
         let mut scenarios = "[".to_string();
         let mut latency_improvements = "[".to_string();
         let mut bandwidth_improvements = "[".to_string();
+        let mut throughput_improvements = "[".to_string();
         let mut packet_loss_improvements = "[".to_string();
         let mut transfer_time_improvements = "[".to_string();
         let mut overall_improvements = "[".to_string();
@@ -259,35 +951,52 @@ impl PerformanceVisualizer {
                 scenarios.push_str(", ");
                 latency_improvements.push_str(", ");
                 bandwidth_improvements.push_str(", ");
+                throughput_improvements.push_str(", ");
                 packet_loss_improvements.push_str(", ");
                 transfer_time_improvements.push_str(", ");
                 overall_improvements.push_str(", ");
             }
-            
+
             scenarios.push_str(&format!("'{}'", name));
-            
+
             if let Some(improvement) = scenario_data.get(name) {
                 latency_improvements.push_str(&format!("{:.2}", improvement.latency));
                 bandwidth_improvements.push_str(&format!("{:.2}", improvement.bandwidth));
+                throughput_improvements.push_str(&format!("{:.2}", improvement.throughput));
                 packet_loss_improvements.push_str(&format!("{:.2}", improvement.packet_loss));
                 transfer_time_improvements.push_str(&format!("{:.2}", improvement.transfer_time));
                 overall_improvements.push_str(&format!("{:.2}", improvement.overall));
             } else {
                 latency_improvements.push_str("0");
                 bandwidth_improvements.push_str("0");
+                throughput_improvements.push_str("0");
                 packet_loss_improvements.push_str("0");
                 transfer_time_improvements.push_str("0");
                 overall_improvements.push_str("0");
             }
         }
-        
+
         scenarios.push_str("]");
         latency_improvements.push_str("]");
         bandwidth_improvements.push_str("]");
+        throughput_improvements.push_str("]");
         packet_loss_improvements.push_str("]");
         transfer_time_improvements.push_str("]");
         overall_improvements.push_str("]");
-        
+
+        // Raw Kbps series (not improvement percentages) for the bitrate/throughput overlay
+        let bitrate_values: Vec<String> = scenario_names.iter()
+            .map(|name| format!("{:.2}", bitrate_throughput_data.get(*name).map(|(b, _)| *b).unwrap_or(0.0)))
+            .collect();
+        let throughput_values: Vec<String> = scenario_names.iter()
+            .map(|name| format!("{:.2}", bitrate_throughput_data.get(*name).map(|(_, t)| *t).unwrap_or(0.0)))
+            .collect();
+        let bitrate_values = format!("[{}]", bitrate_values.join(", "));
+        let throughput_values = format!("[{}]", throughput_values.join(", "));
+
+        let (timeseries_canvases, timeseries_script) = self.generate_timeseries_section(window_secs);
+        let (timeline_canvas, timeline_script) = self.generate_timeline_section();
+
         format!(r#"<!DOCTYPE html>
 <html>
 <head>
@@ -296,11 +1005,14 @@ impl PerformanceVisualizer {
     <style>
         body {{ font-family: Arial, sans-serif; margin: 20px; }}
         h1 {{ color: #2c3e50; }}
-        .chart-container {{ 
+        .chart-container {{
             display: flex;
             flex-wrap: wrap;
             justify-content: space-between;
         }}
+        .timeline-item {{
+            width: 100%;
+        }}
         .chart-item {{ 
             width: 48%; 
             margin-bottom: 20px;
@@ -345,6 +1057,9 @@ impl PerformanceVisualizer {
         <div class="chart-item">
             <canvas id="bandwidthChart"></canvas>
         </div>
+        <div class="chart-item">
+            <canvas id="throughputChart"></canvas>
+        </div>
         <div class="chart-item">
             <canvas id="packetLossChart"></canvas>
         </div>
@@ -354,16 +1069,31 @@ impl PerformanceVisualizer {
         <div class="chart-item">
             <canvas id="radarChart"></canvas>
         </div>
+        <div class="chart-item">
+            <canvas id="bitrateThroughputChart"></canvas>
+        </div>
     </div>
-    
+
+    <h2>Protocol Adaptation Timeline</h2>
+    <p>Hover a bar to highlight the metric samples recorded during that interval across the time-series charts below.</p>
+    <div class="chart-container">
+{timeline_canvas}    </div>
+
+    <h2>Improvement Over Time ({window_secs}s windows)</h2>
+    <div class="chart-container">
+{timeseries_canvases}    </div>
+
     <script>
         // Chart data
         const scenarios = {scenarios};
         const latencyImprovements = {latency_improvements};
         const bandwidthImprovements = {bandwidth_improvements};
+        const throughputImprovements = {throughput_improvements};
         const packetLossImprovements = {packet_loss_improvements};
         const transferTimeImprovements = {transfer_time_improvements};
         const overallImprovements = {overall_improvements};
+        const bitrateValues = {bitrate_values};
+        const throughputValues = {throughput_values};
         
         // Calculate average improvement
         const avgOverallImprovement = overallImprovements.reduce((a, b) => a + b, 0) / overallImprovements.length;
@@ -380,7 +1110,32 @@ impl PerformanceVisualizer {
             'rgba(201, 203, 207, 0.7)',
             'rgba(255, 99, 255, 0.7)'
         ];
-        
+
+        // Time-series charts register themselves here as they're built below, keyed by
+        // metric, so the adaptation timeline can highlight their points on hover
+        const timeseriesCharts = {{}};
+
+        // Recolor/resize the points of every time-series chart that fall inside
+        // [start, end], restoring the rest to their default style
+        function highlightInterval(start, end) {{
+            Object.values(timeseriesCharts).forEach(chart => {{
+                const dataset = chart.data.datasets[0];
+                const baseColor = dataset.borderColor;
+                dataset.pointBackgroundColor = chart.rawTimes.map(t => (t >= start && t <= end) ? 'rgba(255, 99, 132, 1)' : baseColor);
+                dataset.pointRadius = chart.rawTimes.map(t => (t >= start && t <= end) ? 6 : 3);
+                chart.update('none');
+            }});
+        }}
+
+        function clearHighlight() {{
+            Object.values(timeseriesCharts).forEach(chart => {{
+                const dataset = chart.data.datasets[0];
+                dataset.pointBackgroundColor = dataset.borderColor;
+                dataset.pointRadius = 3;
+                chart.update('none');
+            }});
+        }}
+
         // Overall improvement chart
         const overallCtx = document.getElementById('overallChart').getContext('2d');
         new Chart(overallCtx, {{
@@ -489,6 +1244,42 @@ impl PerformanceVisualizer {
             }}
         }});
         
+        // Throughput improvement chart (achieved goodput, distinct from the configured
+        // bitrate above)
+        const throughputCtx = document.getElementById('throughputChart').getContext('2d');
+        new Chart(throughputCtx, {{
+            type: 'bar',
+            data: {{
+                labels: scenarios,
+                datasets: [{{
+                    label: 'Throughput Improvement (%)',
+                    data: throughputImprovements,
+                    backgroundColor: 'rgba(153, 102, 255, 0.7)',
+                    borderColor: 'rgba(153, 102, 255, 1)',
+                    borderWidth: 1
+                }}]
+            }},
+            options: {{
+                responsive: true,
+                plugins: {{
+                    title: {{
+                        display: true,
+                        text: 'Throughput Improvement by Scenario',
+                        font: {{ size: 16 }}
+                    }}
+                }},
+                scales: {{
+                    y: {{
+                        beginAtZero: true,
+                        title: {{
+                            display: true,
+                            text: 'Improvement %'
+                        }}
+                    }}
+                }}
+            }}
+        }});
+
         // Packet loss improvement chart
         const packetLossCtx = document.getElementById('packetLossChart').getContext('2d');
         new Chart(packetLossCtx, {{
@@ -603,15 +1394,142 @@ impl PerformanceVisualizer {
                 }}
             }}
         }});
+        // Bitrate vs. throughput overlay, raw Kbps rather than an improvement percentage, so
+        // over-driving a degraded link (bitrate up, throughput flat) is visible per scenario
+        const bitrateThroughputCtx = document.getElementById('bitrateThroughputChart').getContext('2d');
+        new Chart(bitrateThroughputCtx, {{
+            type: 'bar',
+            data: {{
+                labels: scenarios,
+                datasets: [
+                    {{
+                        label: 'Configured Bitrate (Kbps)',
+                        data: bitrateValues,
+                        backgroundColor: 'rgba(54, 162, 235, 0.7)',
+                        borderColor: 'rgba(54, 162, 235, 1)',
+                        borderWidth: 1
+                    }},
+                    {{
+                        label: 'Achieved Throughput (Kbps)',
+                        data: throughputValues,
+                        backgroundColor: 'rgba(255, 99, 132, 0.7)',
+                        borderColor: 'rgba(255, 99, 132, 1)',
+                        borderWidth: 1
+                    }}
+                ]
+            }},
+            options: {{
+                responsive: true,
+                plugins: {{
+                    title: {{
+                        display: true,
+                        text: 'Configured Bitrate vs. Achieved Throughput by Scenario',
+                        font: {{ size: 16 }}
+                    }}
+                }},
+                scales: {{
+                    y: {{
+                        beginAtZero: true,
+                        title: {{
+                            display: true,
+                            text: 'Kbps'
+                        }}
+                    }}
+                }}
+            }}
+        }});
+{timeline_script}
+{timeseries_script}
     </script>
 </body>
-</html>"#, 
+</html>"#,
             scenarios = scenarios,
             latency_improvements = latency_improvements,
             bandwidth_improvements = bandwidth_improvements,
+            throughput_improvements = throughput_improvements,
             packet_loss_improvements = packet_loss_improvements,
             transfer_time_improvements = transfer_time_improvements,
-            overall_improvements = overall_improvements
+            overall_improvements = overall_improvements,
+            bitrate_values = bitrate_values,
+            throughput_values = throughput_values,
+            timeline_canvas = timeline_canvas,
+            timeline_script = timeline_script,
+            timeseries_canvases = timeseries_canvases,
+            timeseries_script = timeseries_script,
+            window_secs = window_secs,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(visualizer: &mut PerformanceVisualizer, timestamp: f64, metric: &str, improvement: f64) {
+        visualizer.data_points.push(VisualizationDataPoint {
+            timestamp,
+            scenario: "scenario".to_string(),
+            metric: metric.to_string(),
+            baseline: 0.0,
+            with_adaptation: 0.0,
+            improvement,
+        });
+    }
+
+    #[test]
+    fn windowed_improvement_is_empty_for_a_non_positive_window() {
+        let mut visualizer = PerformanceVisualizer::new();
+        push(&mut visualizer, 0.0, "latency", 10.0);
+
+        assert!(visualizer.windowed_improvement("latency", 0.0, false).is_empty());
+        assert!(visualizer.windowed_improvement("latency", -1.0, false).is_empty());
+    }
+
+    #[test]
+    fn windowed_improvement_is_empty_when_no_points_match_the_metric() {
+        let mut visualizer = PerformanceVisualizer::new();
+        push(&mut visualizer, 0.0, "latency", 10.0);
+
+        assert!(visualizer.windowed_improvement("bandwidth", 10.0, false).is_empty());
+    }
+
+    #[test]
+    fn windowed_improvement_means_per_bin_and_skips_empty_bins() {
+        let mut visualizer = PerformanceVisualizer::new();
+        push(&mut visualizer, 0.0, "latency", 10.0);
+        push(&mut visualizer, 3.0, "latency", 20.0);
+        // Bin [10, 20) is left empty on purpose
+        push(&mut visualizer, 25.0, "latency", 100.0);
+
+        let points = visualizer.windowed_improvement("latency", 10.0, false);
+
+        assert_eq!(points, vec![(0.0, 15.0), (20.0, 100.0)]);
+    }
+
+    #[test]
+    fn windowed_improvement_as_rate_uses_last_minus_first_over_window() {
+        let mut visualizer = PerformanceVisualizer::new();
+        push(&mut visualizer, 0.0, "latency", 10.0);
+        push(&mut visualizer, 3.0, "latency", 20.0);
+        push(&mut visualizer, 25.0, "latency", 100.0);
+
+        let points = visualizer.windowed_improvement("latency", 10.0, true);
+
+        // Bin 0 has two samples: (20.0 - 10.0) / 10.0 = 1.0
+        // Bin for t=25 has a single sample, so last == first: (100.0 - 100.0) / 10.0 = 0.0
+        assert_eq!(points, vec![(0.0, 1.0), (20.0, 0.0)]);
+    }
+
+    #[test]
+    fn windowed_improvement_last_sample_lands_in_the_final_bin() {
+        let mut visualizer = PerformanceVisualizer::new();
+        push(&mut visualizer, 0.0, "latency", 0.0);
+        // Exactly on a bin boundary: t_max == window_secs means bin_count == 2, and this sample's
+        // naive index (t_max / window_secs == 1.0) must land in bin 1, not be clamped into bin 0
+        push(&mut visualizer, 10.0, "latency", 40.0);
+
+        let points = visualizer.windowed_improvement("latency", 10.0, false);
+
+        assert_eq!(points, vec![(0.0, 0.0), (10.0, 40.0)]);
+    }
+}